@@ -39,7 +39,7 @@ impl Trait2 for usize {
 enum Mine {
     V1(i32),
     V2(i32),
-    V3(usize, i32),
+    V3(usize, #[penum(dispatch)] i32),
 }
 
 #[penum( (T) | (U, T) where usize: ^Trait, i32: ^Trait2 )]
@@ -57,7 +57,7 @@ enum Mine3 {
 }
 
 // FIXME: This skips the T dispatch.
-#[penum( (T) | (U, T) where T: ^Trait, T: ^Trait2, usize: ^Trait2 )]
+#[penum( (T) | (U, ^T) where T: ^Trait, T: ^Trait2, usize: ^Trait2 )]
 enum Mine4 {
     V1(i32),
     V2(i32),
@@ -81,7 +81,7 @@ impl Cool for i32 {
 enum Mine5 {
     V1(i32),
     V2(i32),
-    V3(i32, i32),
+    V3(#[penum(dispatch)] i32, i32),
 }
 
 fn main() {
@@ -0,0 +1,29 @@
+use penum::penum;
+
+#[penum]
+pub trait Named {
+    fn name(&self) -> String;
+}
+
+pub mod shapes {
+    use penum::penum;
+
+    #[penum(path = shapes)]
+    pub trait Shape: crate::Named {
+        fn area(&self) -> f32;
+    }
+
+    pub struct Square(pub f32);
+
+    impl crate::Named for Square {
+        fn name(&self) -> String {
+            "square".to_string()
+        }
+    }
+
+    impl Shape for Square {
+        fn area(&self) -> f32 {
+            self.0 * self.0
+        }
+    }
+}
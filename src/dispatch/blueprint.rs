@@ -1,38 +1,85 @@
-use std::borrow::BorrowMut;
+use std::borrow::Cow;
 use std::collections::BTreeMap;
-use std::ops::Deref;
-use std::ops::DerefMut;
+use std::collections::BTreeSet;
 
 use proc_macro2::Ident;
 
 use proc_macro2::Span;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::ToTokens;
 use syn::parse_quote;
-use syn::parse_str;
 use syn::punctuated::Punctuated;
 use syn::token;
-use syn::visit_mut::visit_angle_bracketed_generic_arguments_mut;
+use syn::visit::visit_type;
+use syn::visit::Visit;
+use syn::visit_mut::visit_generics_mut;
+use syn::visit_mut::visit_path_arguments_mut;
 use syn::visit_mut::visit_type_mut;
+use syn::visit_mut::visit_type_reference_mut;
 use syn::visit_mut::VisitMut;
 use syn::Arm;
 use syn::Binding;
+use syn::Expr;
+use syn::Field;
+use syn::FnArg;
 use syn::GenericArgument;
+use syn::ImplItemMethod;
+use syn::ItemEnum;
 use syn::ItemTrait;
-use syn::Token;
+use syn::Lifetime;
+use syn::Pat;
+use syn::PatType;
+use syn::Path;
+use syn::PathArguments;
+use syn::ReturnType;
+use syn::Signature;
 use syn::TraitBound as SynTraitBound;
 use syn::TraitItem;
+use syn::TraitItemConst;
 use syn::TraitItemMethod;
 use syn::TraitItemType;
 use syn::Type;
+use syn::TypeImplTrait;
 use syn::TypeParam;
-
+use syn::TypeParamBound;
+use syn::TypePath;
+use syn::TypeReference;
+use syn::Visibility;
+
+use crate::error::Diagnostic;
+use crate::factory::ArmOverride;
+use crate::factory::Deref;
+use crate::factory::DerefLayer;
+use crate::factory::DispatchOptions;
+use crate::factory::Forward;
+use crate::factory::Mismatch;
+use crate::factory::Subject;
 use crate::factory::TraitBound;
 use crate::polym::UniqueHashId;
-
+use crate::utils::ambiguous_dispatch;
+use crate::utils::ambiguous_trait;
+use crate::utils::conflicting_dispatch_markers;
+use crate::utils::pinned_deref_not_permitted;
+use crate::utils::supertrait_not_found;
+use crate::utils::unknown_arm_method;
+use crate::utils::unknown_sum_type;
+use crate::utils::unqualified_trait;
+use crate::utils::unsupported_sum_type;
+
+use super::registry::find_live_trait;
+use super::registry::find_trait;
+use super::registry::LookupError;
 use super::ret::return_default_ret_type;
 use super::ret::return_panic;
-use super::T_SHM;
 
+use super::sig::get_rebound_params;
+use super::sig::is_pinned;
+use super::sig::is_rust_call;
+use super::sig::rebind_params;
 use super::sig::VariantSig;
+use super::standard::is_fmt_trait;
+use super::standard::is_shared_method;
 use super::standard::StandardTrait;
 use super::standard::TraitSchematic;
 
@@ -68,22 +115,109 @@ pub struct Blueprint<'bound> {
     /// ```
     pub ty: Option<Box<Type>>,
 
-    /// Trait bound
-    pub bound: &'bound TraitBound,
+    /// Trait bound. Owned when the bound has been derived from a
+    /// supertrait of another dispatched trait.
+    pub bound: Cow<'bound, TraitBound>,
 
     /// Trait definition
     pub schematic: TraitSchematic,
 
     /// `method_name -> [Arm]`
     pub methods: BTreeMap<Ident, Vec<Arm>>,
+
+    /// Fields that have the potential of being dispatched. These are
+    /// turned into `methods` once the arbiter has decided which
+    /// candidate to select for each variant.
+    pub candidates: Vec<Candidate>,
+
+    /// Unit variants, which are rebuilt as is by methods that return
+    /// `Self`.
+    pub units: Vec<Ident>,
+
+    /// Every variant of the enum, in order. Used by `mismatch = order`.
+    pub variants: Vec<Ident>,
+
+    /// `variant -> type` of each dispatched field, decided by the
+    /// arbiter. Used to read associated consts of the dispatched types.
+    pub dispatched: Vec<(Ident, Type)>,
+
+    /// Dispatched variants whose field is seen through an `Option`, and
+    /// fall back to a default when it's `None`.
+    pub optional: Vec<Ident>,
+
+    /// `method_name -> [Pat]` of the parameters before they were rebound,
+    /// so that hand-written expressions can refer to them by name.
+    pub params: BTreeMap<Ident, Vec<Pat>>,
+
+    /// `(variant, method_name, expr)` of each `#[penum(arm(..))]`, which
+    /// replaces the dispatched arm of the variant.
+    pub overrides: Vec<(Ident, Ident, Expr)>,
+}
+
+/// A candidate is a field that fits the dispatched trait bound. A
+/// variant can have more than one candidate, but only one of them can
+/// be dispatched. Read more /docs/static-dispatch.md
+#[derive(Clone, Hash, Debug)]
+pub struct Candidate {
+    /// The enum the variant belongs to
+    enum_ident: Ident,
+
+    /// The variant the field belongs to
+    variant: Ident,
+
+    /// The position of the field in the variant
+    index: usize,
+
+    /// User bias, i.e. the field has been marked with `^` or
+    /// `#[penum(dispatch)]`
+    marked: bool,
+
+    /// Only used to report disputes
+    field: Field,
+
+    /// The field is seen through an `Option`, e.g. `Option<Box<T>>`.
+    optional: bool,
+
+    /// `method_name -> Arm`
+    arms: Vec<(Ident, Arm)>,
 }
-// FIXME: Should be by Trait bound instead of by Type?
-// This will stop working when `impl Trait for {A, B}` because
-// they are interpreted as two different impls.
-// `_ where i32: ^Trait, usize: ^Trait`
-#[repr(transparent)]
+
+/// Blueprints are keyed by their normalized trait bound instead of the
+/// bounded type. This lets several concrete types share the same impl,
+/// while generic traits with different arguments are kept apart.
+///
+/// ```text
+/// _ where A: ^Trait, B: ^Trait<X>, C: ^Trait<Y>
+///         |  ^^^^^^  |  ^^^^^^^^^  |  ^^^^^^^^^
+///         |  |       |  |          |  |
+///         |  |       |  |          |  impl Trait<Y> for Enum
+///         |  |       |  |          |
+///         |  |       |  impl Trait<X> for Enum
+///         |  |       |
+///         |  impl Trait for Enum
+///         |
+///         Bounded type, used to find the trait bounds a field should
+///         be attached to.
+/// ```
+///
+/// `impl Trait for {A, B}` is interpreted as `_ where A: ^Trait, B:
+/// ^Trait`, meaning that it also ends up as one impl.
 #[derive(Default, Hash, Debug)]
-pub struct BlueprintsMap<'bound>(BTreeMap<UniqueHashId<Type>, Vec<Blueprint<'bound>>>);
+pub struct BlueprintsMap<'bound> {
+    /// `trait_bound -> Blueprint`
+    blueprints: BTreeMap<UniqueHashId<Type>, Blueprint<'bound>>,
+
+    /// `bounded_type -> [trait_bound]`
+    bounded: BTreeMap<UniqueHashId<Type>, BTreeSet<UniqueHashId<Type>>>,
+
+    /// Dispatched traits that haven't been registered yet. These are
+    /// implemented once the trait gets tagged with `#[penum]`.
+    pending: Vec<TraitBound>,
+
+    /// Dispatched traits from other crates. These are implemented once
+    /// the exported macro of the trait calls us back.
+    foreign: Vec<TraitBound>,
+}
 
 /// Only use this for modifying methods trait generics. Should probably
 /// use visit_mut more often..
@@ -105,7 +239,6 @@ struct MonomorphizeFnSignature<'poly>(&'poly BTreeMap<Ident, &'poly Type>);
 /// |
 /// This one already gets replace during polymophic mapping step.
 /// ```
-#[allow(dead_code)]
 struct MonomorphizeTraitBound<'poly>(&'poly BTreeMap<Ident, &'poly Type>);
 
 ///        
@@ -118,24 +251,32 @@ struct MonomorphizeTraitBound<'poly>(&'poly BTreeMap<Ident, &'poly Type>);
 /// ```
 struct RemoveBoundBindings;
 
+///        
+/// ```text
+/// where i32: Add<Self, Output = Self>
+///                ^^^^           ^^^^
+///                |              |
+///                Replace with the bounded type, i.e. the dispatched
+///                field is paired with, and rebuilt into, `Self`.
+/// ```
+pub struct MonomorphizeSelf<'ty>(pub &'ty Type);
+
 /// FIXME: USE VISITER PATTERN INSTEAD.
 impl<'bound> Blueprint<'bound> {
     /// Should probably be using `visit_mut` more often......
-    pub fn get_associated_methods(&self) -> Vec<TraitItemMethod> {
+    ///
+    /// In `strict` mode, matches that cover every variant have no `_`
+    /// arm, so that rustc checks the exhaustiveness for us.
+    pub fn get_associated_methods(&self, strict: bool) -> Vec<TraitItemMethod> {
         let mut method_items = vec![];
 
-        // This polymap only contains TRAIT GENERIC PARAM MAPPINGS e.g.
-        // A<i32>
-        let polymap = self.get_bound_generics().map(|types| {
-            self.get_schematic_generics()
-                .zip(types)
-                .map(|(gen, ty)| (gen.ident.clone(), ty))
-                .collect::<BTreeMap<_, _>>()
-        });
+        let polymap = self.get_generics_polymap();
+        let sum_returns = self.get_sum_returns();
 
         for method in self.get_schematic_methods() {
-            if let Some(method_arms) = self.methods.get(&method.sig.ident) {
+            if self.is_dispatched_method(&method.sig.ident) {
                 let TraitItemMethod { ref sig, .. } = method;
+                let method_arms = self.methods.get(&sig.ident).into_iter().flatten();
 
                 let mut signature = sig.clone();
 
@@ -143,26 +284,115 @@ impl<'bound> Blueprint<'bound> {
                     MonomorphizeFnSignature(polymap).visit_signature_mut(&mut signature)
                 }
 
-                // Right now, we always default to a panic. But we could
-                // consider other options here too. For example, if we
-                // had an Option return type, we could default with
-                // `None` instead. Read more /docs/static-dispatch.md
+                // Callables are implemented as inherent methods.
+                if is_rust_call(&signature) {
+                    signature.abi = None;
+                }
 
                 // We should look for Default implementations on the
                 // return type. Through, a `-> &T` where `T: Default`.
                 // It's not possible to do `&Default::default()` or
                 // `&T::default()` IIRC. A &T where T isn't owned by
                 // self needs to be ZST to be able to be returned.
-                let default_return = match &signature.output {
-                    syn::ReturnType::Default => quote::quote!(()),
-                    syn::ReturnType::Type(_, ty) => {
-                        return_default_ret_type(ty).unwrap_or_else(return_panic)
-                    }
+                // Generated types of `-> impl Trait` have an empty variant.
+                let either_return = sum_returns
+                    .get(&method.sig.ident)
+                    .and_then(SumReturn::get_fallback);
+
+                let default_return =
+                    either_return.unwrap_or_else(|| get_default_return(&signature));
+
+                // Methods taking `Self` arguments match on every argument,
+                // e.g. `match (self, other)`.
+                let pairs = get_pair_params(&signature);
+
+                let user_fallback = self
+                    .bound
+                    .get_fallbacks()
+                    .into_iter()
+                    .find(|(ident, _)| ident.eq(&sig.ident));
+
+                let fallback = match (user_fallback, pairs.as_slice(), self.bound.get_mismatch()) {
+                    (Some((_, expr)), _, _) => self.bind_params(&method, &expr).into_token_stream(),
+                    (None, [], _) | (None, _, Mismatch::Default) => default_return,
+                    (None, _, Mismatch::Panic) => return_panic(),
+                    (None, [other], Mismatch::Order) => self.get_order_return(&signature, other),
+                    // There is no order for more than two variants.
+                    (None, _, Mismatch::Order) => return_panic(),
+                };
+
+                // Hand-written arms, e.g. `Self::Bar { .. } => write!(f, "bar")`.
+                let override_arms = self
+                    .overrides
+                    .iter()
+                    .filter(|(_, ident, _)| ident.eq(&sig.ident))
+                    .map(|(variant, _, expr)| -> Arm {
+                        let expr = self.bind_params(&method, expr);
+
+                        if pairs.is_empty() {
+                            parse_quote!(Self::#variant { .. } => #expr)
+                        } else {
+                            let others = pairs.iter().map(|_| quote::quote!(_));
+                            parse_quote!((Self::#variant { .. }, #(#others),*) => #expr)
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                // Unit variants can always be rebuilt, e.g. `Foo::Bar => Foo::Bar`,
+                // and are always equal to themselves, e.g. `(Foo::Bar, Foo::Bar) => true`.
+                let unit_return = if self.returns_self(&method) {
+                    None
+                } else if let ([_], true) = (pairs.as_slice(), is_comparison(&signature)) {
+                    get_ordering_return(&signature).map(|ordering| {
+                        quote::quote!({
+                            let ordering = ::core::cmp::Ordering::Equal;
+                            #ordering
+                        })
+                    })
+                } else {
+                    None
+                };
+
+                let unit_arms = self
+                    .units
+                    .iter()
+                    .filter(|_| self.returns_self(&method) || unit_return.is_some())
+                    .filter(|unit| !self.is_overridden(unit, &sig.ident))
+                    .map(|unit| -> Arm {
+                        let value = unit_return
+                            .clone()
+                            .unwrap_or_else(|| quote::quote!(Self::#unit));
+
+                        if pairs.is_empty() {
+                            parse_quote!(Self::#unit => #value)
+                        } else {
+                            let others = pairs.iter().map(|_| quote::quote!(Self::#unit));
+                            parse_quote!((Self::#unit, #(#others),*) => #value)
+                        }
+                    });
+
+                let scrutinee = if is_pinned(&signature) {
+                    // SAFETY: The variant fields are only handed out pinned,
+                    // see `dispatch::pin`, so nothing is moved out of `self`.
+                    quote::quote!(unsafe { ::core::pin::Pin::get_unchecked_mut(self) })
+                } else if pairs.is_empty() {
+                    quote::quote!(self)
+                } else {
+                    quote::quote!((self, #(#pairs),*))
                 };
 
+                let exhaustive =
+                    strict && pairs.is_empty() && self.get_uncovered_variants(&method).is_empty();
+                let fallback = (!exhaustive).then(|| quote::quote!(_ => #fallback));
+
                 // A method item that is ready to be implemented
                 let item: TraitItemMethod = parse_quote!(
-                    #signature { match self { #(#method_arms,)* _ => #default_return } }
+                    #signature { match #scrutinee {
+                        #(#method_arms,)*
+                        #(#override_arms,)*
+                        #(#unit_arms,)*
+                        #fallback
+                    } }
                 );
 
                 method_items.push(item);
@@ -171,6 +401,579 @@ impl<'bound> Blueprint<'bound> {
         method_items
     }
 
+    /// Associated consts can't be dispatched through the trait, as the
+    /// enum would need one value for every variant. They are read
+    /// through inherent `const fn` accessors instead.
+    ///
+    /// ```text
+    /// trait Tag { const ID: u32; }
+    ///
+    /// pub const fn id(&self) -> u32 {
+    ///     match self { Self::A { .. } => <A as Tag>::ID, .. }
+    /// }
+    /// ```
+    pub fn get_const_accessors(&self, vis: &Visibility, strict: bool) -> Vec<ImplItemMethod> {
+        let trait_path = self.get_sanatized_impl_path();
+        let polymap = self.get_generics_polymap();
+
+        self.get_schematic_consts()
+            .map(|item| {
+                let TraitItemConst {
+                    attrs, ident, ty, ..
+                } = item;
+
+                let mut ty = ty.clone();
+
+                if let Some(polymap) = polymap.as_ref() {
+                    MonomorphizeFnSignature(polymap).visit_type_mut(&mut ty)
+                }
+
+                let name =
+                    format_ident!("{}", ident.to_string().to_lowercase(), span = ident.span());
+                let default_return = return_default_ret_type(&ty).unwrap_or_else(return_panic);
+
+                let arms = self.dispatched.iter().map(|(variant, field_ty)| {
+                    quote::quote!(Self::#variant { .. } => <#field_ty as #trait_path>::#ident)
+                });
+
+                let exhaustive = strict && self.get_undispatched_variants().next().is_none();
+                let fallback = (!exhaustive).then(|| quote::quote!(_ => #default_return));
+
+                parse_quote!(
+                    #(#attrs)*
+                    #vis const fn #name(&self) -> #ty {
+                        match self {
+                            #(#arms,)*
+                            #fallback
+                        }
+                    }
+                )
+            })
+            .collect()
+    }
+
+    /// Variants that have no field to dispatch to.
+    fn get_undispatched_variants(&self) -> impl Iterator<Item = &Ident> {
+        self.variants
+            .iter()
+            .filter(|variant| !self.dispatched.iter().any(|(ident, _)| ident == *variant))
+    }
+
+    /// Variants that `method` has no arm for, and therefore fall back to
+    /// a default. Unit variants are rebuilt by methods returning `Self`.
+    fn get_uncovered_variants(&self, method: &TraitItemMethod) -> Vec<&Ident> {
+        let returns_self = self.returns_self(method);
+
+        self.get_undispatched_variants()
+            .filter(|variant| !(returns_self && self.units.contains(variant)))
+            .filter(|variant| !self.is_overridden(variant, &method.sig.ident))
+            .collect()
+    }
+
+    /// Variants whose dispatched field is seen through an `Option`, which
+    /// `method` falls back to a default for when it's `None`. See
+    /// `VariantSig::parse_deref_arm`.
+    fn get_optional_variants(&self, method: &TraitItemMethod) -> Vec<&Ident> {
+        if self.returns_self(method) || !self.get_method_pair_params(method).is_empty() {
+            return vec![];
+        }
+
+        self.optional
+            .iter()
+            .filter(|variant| !self.is_overridden(variant, &method.sig.ident))
+            .collect()
+    }
+
+    /// Methods that have at least one arm, dispatched or hand-written.
+    fn is_dispatched_method(&self, method: &Ident) -> bool {
+        self.methods.contains_key(method)
+            || self.overrides.iter().any(|(_, ident, _)| ident == method)
+    }
+
+    fn is_overridden(&self, variant: &Ident, method: &Ident) -> bool {
+        self.overrides
+            .iter()
+            .any(|(ident, name, _)| ident == variant && name == method)
+    }
+
+    /// Binds the parameters of `method` to the names they have in the
+    /// trait, so that hand-written expressions can use them, e.g. `f` in
+    /// `write!(f, "none")`.
+    fn bind_params(&self, method: &TraitItemMethod, expr: &Expr) -> Expr {
+        let params = self
+            .params
+            .get(&method.sig.ident)
+            .cloned()
+            .unwrap_or_default();
+        let args = get_rebound_params(&method.sig);
+
+        if params.is_empty() {
+            return expr.clone();
+        }
+
+        parse_quote!({
+            #[allow(unused_variables, unused_mut)]
+            let (#(#params,)*) = (#(#args,)*);
+            #expr
+        })
+    }
+
+    /// Methods and const accessors that would panic for some variants,
+    /// together with those variants. Only reported in `strict` mode.
+    ///
+    /// Methods taking `Self` arguments are left out, as what they return
+    /// for different variants is decided by `mismatch`.
+    pub fn get_missing_arms(&self) -> Vec<(Ident, Vec<Ident>)> {
+        let polymap = self.get_generics_polymap();
+        let sum_returns = self.get_sum_returns();
+
+        let mut missing = vec![];
+
+        let fallbacks = self.bound.get_fallbacks();
+
+        for method in self.get_schematic_methods() {
+            if !self.is_dispatched_method(&method.sig.ident) {
+                continue;
+            }
+
+            let mut signature = method.sig.clone();
+
+            if let Some(polymap) = polymap.as_ref() {
+                MonomorphizeFnSignature(polymap).visit_signature_mut(&mut signature)
+            }
+
+            let has_fallback = !get_pair_params(&signature).is_empty()
+                || fallbacks
+                    .iter()
+                    .any(|(ident, _)| ident.eq(&method.sig.ident))
+                || sum_returns
+                    .get(&method.sig.ident)
+                    .and_then(SumReturn::get_fallback)
+                    .is_some()
+                || match &signature.output {
+                    ReturnType::Default => true,
+                    ReturnType::Type(_, ty) => return_default_ret_type(ty).is_some(),
+                };
+
+            let mut uncovered = self.get_uncovered_variants(&method);
+            uncovered.extend(self.get_optional_variants(&method));
+
+            if !has_fallback && !uncovered.is_empty() {
+                missing.push((
+                    method.sig.ident.clone(),
+                    uncovered.into_iter().cloned().collect(),
+                ));
+            }
+        }
+
+        for item in self.get_schematic_consts() {
+            let mut ty = item.ty.clone();
+
+            if let Some(polymap) = polymap.as_ref() {
+                MonomorphizeFnSignature(polymap).visit_type_mut(&mut ty)
+            }
+
+            let uncovered = self
+                .get_undispatched_variants()
+                .cloned()
+                .collect::<Vec<_>>();
+
+            if return_default_ret_type(&ty).is_none() && !uncovered.is_empty() {
+                let name = format_ident!("{}", item.ident.to_string().to_lowercase());
+                missing.push((name, uncovered));
+            }
+        }
+
+        missing
+    }
+
+    /// Generates a sum type for each associated type in `sum = [..]`,
+    /// with one variant for every distinct dispatched type.
+    ///
+    /// ```text
+    /// ^Iterator(sum = [Item])
+    ///
+    /// enum SourceItem {
+    ///     Lines(<Lines as Iterator>::Item),
+    ///     Bytes(<Bytes as Iterator>::Item),
+    /// }
+    /// ```
+    ///
+    /// Methods returning `impl Trait` and associated types bound by a
+    /// trait, e.g. `IntoIterator::IntoIter`, get a hidden enum of the
+    /// same shape instead, which the trait is then dispatched on.
+    /// These also have an `__Empty` variant that is returned by the
+    /// fallback arm.
+    ///
+    /// ```text
+    /// fn points(&self) -> impl Iterator<Item = u32>;
+    ///
+    /// enum __ShapePoints<T0, T1> {
+    ///     Circle(T0),
+    ///     Square(T1),
+    ///     __Empty,
+    /// }
+    /// ```
+    pub fn get_generated_types(&self, subject: &Subject) -> Vec<GeneratedType> {
+        let trait_path = self.get_sanatized_impl_path();
+        let Subject {
+            vis,
+            ident,
+            generics,
+            ..
+        } = subject;
+        let (_, ty_generics, where_clause) = generics.split_for_impl();
+        let this: Type = parse_quote!(#ident #ty_generics);
+        let bindings = self.get_mapped_bindings().unwrap_or_default();
+
+        let mut generated = vec![];
+
+        for assoc in self.bound.get_sum() {
+            if self.get_schematic_types().all(|ty| ty.ident.ne(&assoc)) {
+                continue;
+            }
+
+            let sum_ident = format_ident!("{}{}", ident, assoc);
+            let variants = self
+                .get_distinct_dispatched()
+                .map(|(variant, ty)| quote::quote!(#variant(<#ty as #trait_path>::#assoc)))
+                .collect::<Vec<_>>();
+
+            generated.push(GeneratedType {
+                source: assoc.clone(),
+                assoc: Some(assoc),
+                item: parse_quote!(
+                    #vis enum #sum_ident #generics #where_clause {
+                        #(#variants),*
+                    }
+                ),
+                bounds: vec![],
+            });
+        }
+
+        for item in self.get_either_types() {
+            let assoc = &item.ident;
+            let either_ident = format_ident!("__{}{}", ident, assoc);
+            let variants = self
+                .get_distinct_dispatched()
+                .map(|(variant, ty)| quote::quote!(#variant(<#ty as #trait_path>::#assoc)));
+
+            generated.push(GeneratedType {
+                assoc: Some(assoc.clone()),
+                source: assoc.clone(),
+                item: parse_quote!(
+                    #[doc(hidden)]
+                    #vis enum #either_ident #generics #where_clause {
+                        #(#variants,)*
+                        __Empty
+                    }
+                ),
+                bounds: get_either_bounds(&item.bounds, &bindings, &this),
+            });
+        }
+
+        for method in self.get_either_methods() {
+            let Some(bounds) = get_impl_trait_bounds(&method.sig.output) else {
+                continue;
+            };
+
+            let either_ident = format_ident!("__{}{}", ident, to_upper_camel(&method.sig.ident));
+            let (params, variants): (Vec<_>, Vec<_>) = self
+                .get_distinct_dispatched()
+                .enumerate()
+                .map(|(index, (variant, _))| {
+                    let param = format_ident!("T{}", index);
+                    (param.clone(), quote::quote!(#variant(#param)))
+                })
+                .unzip();
+
+            let bounds = get_either_bounds(&bounds, &bindings, &this);
+            let bound = quote::quote!(#(#bounds)+*);
+
+            generated.push(GeneratedType {
+                assoc: None,
+                source: method.sig.ident.clone(),
+                item: parse_quote!(
+                    #[doc(hidden)]
+                    #vis enum #either_ident <#(#params),*> where #(#params: #bound),* {
+                        #(#variants,)*
+                        __Empty
+                    }
+                ),
+                bounds,
+            });
+        }
+
+        generated
+    }
+
+    /// Used to report associated types in `sum = [..]` that the trait
+    /// doesn't have, or that are used in a way that cannot be wrapped.
+    pub fn get_sum_errors(&self) -> Vec<(Ident, String)> {
+        let name = self.bound.get_ident();
+        let mut errors = vec![];
+
+        for assoc in self.bound.get_sum() {
+            if self.get_schematic_types().all(|ty| ty.ident.ne(&assoc)) {
+                errors.push((assoc.clone(), unknown_sum_type(&assoc, &name)));
+                continue;
+            }
+
+            for method in self.get_schematic_methods() {
+                if !self.is_forwarded(&method) {
+                    continue;
+                }
+
+                let mut uses = FindAssocType(&assoc, 0);
+                uses.visit_signature(&method.sig);
+
+                let wrapped = SumReturn::new(&method.sig.output, &assoc, assoc.clone()).is_some();
+
+                if uses.1 > usize::from(wrapped) {
+                    errors.push((
+                        assoc.clone(),
+                        unsupported_sum_type(&assoc, &method.sig.ident),
+                    ));
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// `method -> SumReturn` of forwarded methods that return one of the
+    /// types in `get_generated_types`.
+    fn get_sum_returns(&self) -> BTreeMap<Ident, SumReturn> {
+        let Some(enum_ident) = self.candidates.first().map(|c| &c.enum_ident) else {
+            return BTreeMap::new();
+        };
+
+        let sum = self.bound.get_sum();
+        let either_types = self.get_either_types();
+        let either_methods = self.get_either_methods();
+
+        self.get_schematic_methods()
+            .filter_map(|method| {
+                let output = &method.sig.output;
+
+                let sum_return = sum
+                    .iter()
+                    .find_map(|assoc| {
+                        SumReturn::new(output, assoc, format_ident!("{}{}", enum_ident, assoc))
+                    })
+                    .or_else(|| {
+                        either_types.iter().find_map(|item| {
+                            let assoc = &item.ident;
+                            let either_ident = format_ident!("__{}{}", enum_ident, assoc);
+
+                            SumReturn::new(output, assoc, either_ident).map(SumReturn::into_either)
+                        })
+                    })
+                    .or_else(|| {
+                        let ident = &method.sig.ident;
+
+                        either_methods
+                            .iter()
+                            .any(|m| m.sig.ident.eq(ident))
+                            .then(|| {
+                                SumReturn::Either(format_ident!(
+                                    "__{}{}",
+                                    enum_ident,
+                                    to_upper_camel(ident)
+                                ))
+                            })
+                    })?;
+
+                Some((method.sig.ident.clone(), sum_return))
+            })
+            .collect()
+    }
+
+    /// Variants that dispatch to different types end up with different
+    /// associated types, and a variant that isn't dispatched has
+    /// nothing to return. In both cases the returned type has to be
+    /// generated, see `get_generated_types`.
+    ///
+    /// NOTE: This is used before the arbiter has decided which fields to
+    /// dispatch, so we have to look at the candidates.
+    fn needs_either(&self) -> bool {
+        let mut types = self.candidates.iter().map(|c| &c.field.ty);
+        let first = types.next();
+
+        types.any(|ty| Some(ty).ne(&first))
+            || self
+                .variants
+                .iter()
+                .any(|variant| self.candidates.iter().all(|c| c.variant.ne(variant)))
+    }
+
+    /// Associated types bound by a trait, e.g. `type IntoIter: Iterator`,
+    /// that haven't been bound by the user or listed in `sum = [..]`.
+    fn get_either_types(&self) -> Vec<TraitItemType> {
+        if !self.needs_either() {
+            return vec![];
+        }
+
+        let sum = self.bound.get_sum();
+        let bound = self
+            .get_bound_bindings()
+            .map(|bindings| bindings.map(|b| b.ident.clone()).collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        // NOTE: `get_schematic_types` strips the bounds we're looking for.
+        self.schematic
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                TraitItem::Type(ty) => Some(ty.clone()),
+                _ => None,
+            })
+            .filter(|item| {
+                item.default.is_none()
+                    && !sum.contains(&item.ident)
+                    && !bound.contains(&item.ident)
+                    && item.bounds.iter().any(|bound| {
+                        matches!(bound, syn::TypeParamBound::Trait(trait_bound)
+                            if matches!(trait_bound.modifier, syn::TraitBoundModifier::None))
+                    })
+            })
+            .collect()
+    }
+
+    /// Forwarded methods that return `impl Trait`.
+    fn get_either_methods(&self) -> Vec<TraitItemMethod> {
+        if !self.needs_either() {
+            return vec![];
+        }
+
+        let polymap = self.get_generics_polymap();
+
+        self.get_schematic_methods()
+            .filter(|method| self.is_forwarded(method))
+            .map(|mut method| {
+                if let Some(polymap) = polymap.as_ref() {
+                    MonomorphizeFnSignature(polymap).visit_signature_mut(&mut method.sig)
+                }
+                method
+            })
+            .filter(|method| get_impl_trait_bounds(&method.sig.output).is_some())
+            .collect()
+    }
+
+    /// Dispatched types that have the same type share a variant in the
+    /// generated types, named after the first variant.
+    fn get_distinct_dispatched(&self) -> impl Iterator<Item = &(Ident, Type)> {
+        self.dispatched
+            .iter()
+            .enumerate()
+            .filter(|(index, (_, ty))| {
+                !self.dispatched[..*index]
+                    .iter()
+                    .any(|(_, other)| other.eq(ty))
+            })
+            .map(|(_, dispatched)| dispatched)
+    }
+
+    /// `^Fn(..)`, `^FnMut(..)` and `^FnOnce(..)` are dispatched through
+    /// inherent methods, see `get_callable_schematic`.
+    pub fn is_callable(&self) -> bool {
+        self.get_schematic_methods()
+            .any(|method| is_rust_call(&method.sig))
+    }
+
+    /// Adapts the enum into a closure, i.e. `to_fn(&self) -> impl Fn(..)`
+    /// for `^Fn(..)` and `to_fn_mut(&mut self) -> impl FnMut(..)` for
+    /// `^FnMut(..)`.
+    pub fn get_callable_adapter(&self, vis: &Visibility) -> Option<ImplItemMethod> {
+        let Type::Path(TypePath { path, .. }) = &self.bound.ty else {
+            return None;
+        };
+
+        let (name, receiver, method) = match StandardTrait::try_from(path).ok()? {
+            StandardTrait::Fn => ("to_fn", quote::quote!(&self), "call"),
+            StandardTrait::FnMut => ("to_fn_mut", quote::quote!(&mut self), "call_mut"),
+            _ => return None,
+        };
+
+        let call = self
+            .get_schematic_methods()
+            .find(|item| item.sig.ident == method)?;
+
+        let name = format_ident!("{}", name);
+        let method = &call.sig.ident;
+        let bound = &self.bound.ty;
+
+        // The types of the arguments are left to be inferred from `#bound`,
+        // as they might use the lifetimes of `call`.
+        let args = call
+            .sig
+            .inputs
+            .iter()
+            .skip(1)
+            .map(|param| match param {
+                FnArg::Typed(PatType { pat, .. }) => pat.to_token_stream(),
+                FnArg::Receiver(_) => TokenStream::new(),
+            })
+            .collect::<Vec<_>>();
+
+        Some(parse_quote!(
+            #vis fn #name(#receiver) -> impl #bound + '_ {
+                move |#(#args),*| self.#method(#(#args),*)
+            }
+        ))
+    }
+
+    /// Dispatched types that are pinned by methods taking
+    /// `self: Pin<&mut Self>`, see `dispatch::pin`.
+    pub fn get_pinned_types(&self) -> Vec<Type> {
+        let pinned = self
+            .get_schematic_methods()
+            .any(|method| self.methods.contains_key(&method.sig.ident) && is_pinned(&method.sig));
+
+        if !pinned {
+            return vec![];
+        }
+
+        self.get_distinct_dispatched()
+            .map(|(_, ty)| ty.clone())
+            .collect()
+    }
+
+    /// A forwarded method taking `self: Pin<&mut Self>` of a bound that
+    /// sees through wrappers. A pinned enum doesn't pin what a `&mut T`
+    /// or an `Rc<T>` points to, so the pin can't be projected onto it.
+    pub fn get_pinned_deref(&self) -> Option<Ident> {
+        if matches!(self.bound.get_deref(), Deref::None) {
+            return None;
+        }
+
+        self.get_schematic_methods()
+            .find(|method| self.is_forwarded(method) && is_pinned(&method.sig))
+            .map(|method| method.sig.ident)
+    }
+
+    /// A trait with associated consts that have no default cannot be
+    /// implemented for the enum. See `get_const_accessors`.
+    pub fn has_required_consts(&self) -> bool {
+        self.get_schematic_consts()
+            .any(|item| item.default.is_none())
+    }
+
+    /// The methods of a trait that cannot be implemented, see
+    /// `has_required_consts`. These are dispatched as inherent methods
+    /// instead, so `Self::Item` is replaced with the type it's bound to.
+    pub fn get_inherent_methods(&self, strict: bool) -> Vec<TraitItemMethod> {
+        let mut methods = self.get_associated_methods(strict);
+
+        if let Some(bindings) = self.get_mapped_bindings() {
+            for method in methods.iter_mut() {
+                ReplaceSelfAssoc(&bindings).visit_trait_item_method_mut(method);
+            }
+        }
+
+        methods
+    }
+
     /// Used to zip `get_bound_bindings` and `get_schematic_types`
     /// together.
     ///
@@ -262,6 +1065,25 @@ impl<'bound> Blueprint<'bound> {
                 }
             }
 
+            // Associated types that weren't bound, e.g. `IntoIter` in
+            // `IntoIterator<Item = u32>`.
+            for matc in types.iter_mut() {
+                if matc.default.is_none() && self.ty.is_some() {
+                    let ty = &self.ty;
+                    let bound = &self.bound;
+
+                    let ident = &matc.ident;
+                    let generics = &matc.generics;
+
+                    matc.default = Some((
+                        token::Eq(Span::call_site()),
+                        parse_quote!(
+                            <#ty as #bound>::#ident #generics
+                        ),
+                    ));
+                }
+            }
+
             Some(types)
         }
     }
@@ -269,42 +1091,905 @@ impl<'bound> Blueprint<'bound> {
     /// Fill our blueprint with dispatchable variant arms that we later
     /// use to contruct an impl statement.
     pub fn attach(&mut self, variant_sig: &VariantSig) {
-        let mut arms: BTreeMap<Ident, Vec<Arm>> = Default::default();
+        let variant = variant_sig.get_variant_ident();
+        let index = variant_sig.get_field_index();
+
+        // A field can be attached more than once if both its pattern type
+        // and its concrete type are bound to the same trait.
+        if self
+            .candidates
+            .iter()
+            .any(|candidate| candidate.variant.eq(variant) && candidate.index == index)
+        {
+            return;
+        }
+
+        let mut arms = vec![];
+
+        let polymap = self.get_generics_polymap();
+        let (layers, inner_ty) = self.bound.get_deref().unwrap(&variant_sig.get_field().ty);
 
         for item in self.schematic.items.iter() {
             let TraitItem::Method(method) = item else {
                 continue;
             };
 
+            if !self.is_forwarded(method) {
+                continue;
+            }
+
             // FIXME: FILTER RECEIVER METHODS.
 
-            let (method_name, parsed_arm) = variant_sig.parse_arm(method);
+            let trait_path = is_shared_method(&method.sig.ident).then(|| self.get_call_path());
+            let pairs = self.get_method_pair_params(method);
+
+            // Methods that return `Self` or pair variants are called on
+            // the wrapper itself, as it's what the variant holds.
+            let (method_name, parsed_arm) = if self.returns_self(method) {
+                variant_sig.parse_wrapped_arm(method, trait_path.as_ref(), &pairs)
+            } else if !layers.is_empty() && pairs.is_empty() {
+                let mut signature = method.sig.clone();
+
+                if let Some(polymap) = polymap.as_ref() {
+                    MonomorphizeFnSignature(polymap).visit_signature_mut(&mut signature)
+                }
+
+                // `None` falls back the same way as variants without a field.
+                let fallback = self
+                    .bound
+                    .get_fallbacks()
+                    .into_iter()
+                    .find(|(ident, _)| ident.eq(&method.sig.ident))
+                    .map(|(_, expr)| self.bind_params(method, &expr).into_token_stream())
+                    .unwrap_or_else(|| get_default_return(&signature));
 
-            if let Some(arm_vec) = arms.get_mut(method_name) {
-                arm_vec.push(parsed_arm)
+                variant_sig.parse_deref_arm(method, trait_path.as_ref(), &layers, &fallback)
             } else {
-                arms.insert(method_name.clone(), vec![parsed_arm]);
-            }
+                variant_sig.parse_arm(method, trait_path.as_ref(), &pairs)
+            };
+
+            arms.push((method_name.clone(), parsed_arm));
         }
 
-        arms.into_iter().for_each(|(method_name, mut am)| {
-            if let Some(arm_vec) = self.methods.get_mut(&method_name) {
-                arm_vec.append(&mut am);
-            } else {
-                self.methods.insert(method_name, am);
-            }
-        })
+        // The wrapped type is the one that is dispatched to, e.g. `Expr`
+        // in `Box<Expr>`.
+        let mut field = variant_sig.get_field().clone();
+        field.ty = inner_ty.clone();
+
+        self.candidates.push(Candidate {
+            enum_ident: variant_sig.get_enum_ident().clone(),
+            variant: variant.clone(),
+            index,
+            marked: variant_sig.is_marked(),
+            field,
+            optional: layers.contains(&DerefLayer::Option),
+            arms,
+        });
     }
 
-    pub fn get_sanatized_impl_path(&self) -> SynTraitBound {
-        let tb = self.bound.clone();
-        let mut tb: SynTraitBound = parse_quote!(#tb);
-        RemoveBoundBindings.visit_trait_bound_mut(&mut tb);
-        tb
+    /// Required methods are always forwarded, while provided methods
+    /// are only forwarded when the trait bound asks for them, e.g.
+    /// `^Iterator(forward = all)`.
+    fn is_forwarded(&self, method: &TraitItemMethod) -> bool {
+        method.default.is_none() || self.bound.get_forward().includes(&method.sig.ident)
+    }
+
+    /// Methods that return `Self`, or an associated type that has been
+    /// bound to `Self`, e.g. `Neg<Output = Self>`, have to rebuild the
+    /// variant they were dispatched from.
+    fn returns_self(&self, method: &TraitItemMethod) -> bool {
+        let ReturnType::Type(_, ty) = &method.sig.output else {
+            return false;
+        };
+
+        let Type::Path(TypePath { qself: None, path }) = ty.as_ref() else {
+            return false;
+        };
+
+        let assoc = match path.segments.iter().collect::<Vec<_>>().as_slice() {
+            [segment] if segment.ident == "Self" => return true,
+            [segment, assoc] if segment.ident == "Self" => &assoc.ident,
+            [assoc] => &assoc.ident,
+            _ => return false,
+        };
+
+        self.get_bound_bindings().is_some_and(|mut bindings| {
+            bindings.any(|binding| {
+                binding.ident.eq(assoc)
+                    && matches!(&binding.ty, Type::Path(ty) if ty.path.is_ident("Self"))
+            })
+        })
+    }
+
+    /// See `get_pair_params`. Trait generics are substituted first, so
+    /// `other: &Rhs` in `PartialEq<Rhs = Self>` is a pair as well.
+    fn get_method_pair_params(&self, method: &TraitItemMethod) -> Vec<Ident> {
+        let mut signature = method.sig.clone();
+
+        if let Some(polymap) = self.get_generics_polymap().as_ref() {
+            MonomorphizeFnSignature(polymap).visit_signature_mut(&mut signature)
+        }
+
+        get_pair_params(&signature)
+    }
+
+    /// Used with `mismatch = order` to compare the position of the
+    /// variants instead, e.g. `order(self).cmp(&order(other))`. The
+    /// ordering is converted into whatever the method returns.
+    fn get_order_return(&self, signature: &Signature, other: &Ident) -> TokenStream {
+        let variants = &self.variants;
+        let positions = 0..variants.len();
+
+        let Some(ordering) = get_ordering_return(signature) else {
+            return return_panic();
+        };
+
+        quote::quote!({
+            let order = |value: &Self| match value {
+                #(Self::#variants { .. } => #positions,)*
+            };
+            let ordering = order(self).cmp(&order(#other));
+            #ordering
+        })
+    }
+
+    /// An explicit bound that leaves out `mismatch` or `deref` takes them
+    /// from the trait it is a supertrait of, so that e.g.
+    /// `^Ord(mismatch = order) + ^PartialOrd` orders different variants
+    /// the same way in both impls.
+    fn inherit_options(&mut self, supertrait: &TraitBound) {
+        let Some((paren, inherited)) = supertrait.options.as_ref() else {
+            return;
+        };
+
+        let (_, options) = self
+            .bound
+            .to_mut()
+            .options
+            .get_or_insert_with(|| (*paren, Default::default()));
+
+        if options.mismatch == Mismatch::Default {
+            options.mismatch = inherited.mismatch;
+        }
+
+        if options.deref == Deref::default() {
+            options.deref = inherited.deref.clone();
+        }
+    }
+
+    /// Methods in `^Trait(forward = [..])` that the trait doesn't have.
+    pub fn get_unknown_forwards(&self) -> Vec<Ident> {
+        let Forward::Methods(methods) = self.bound.get_forward() else {
+            return vec![];
+        };
+
+        methods
+            .into_iter()
+            .filter(|ident| {
+                self.get_schematic_methods()
+                    .all(|method| method.sig.ident.ne(ident))
+            })
+            .collect()
+    }
+
+    /// Fallbacks of methods that don't exist in the trait.
+    pub fn get_unknown_fallbacks(&self) -> Vec<Ident> {
+        self.bound
+            .get_fallbacks()
+            .into_iter()
+            .map(|(ident, _)| ident)
+            .filter(|ident| {
+                self.get_schematic_methods()
+                    .all(|method| method.sig.ident.ne(ident))
+            })
+            .collect()
+    }
+
+    /// Acts as the arbiter that selects which candidate should be
+    /// dispatched for each variant.
+    ///
+    /// 1. User bias       - a field marked with `^` or `#[penum(dispatch)]`.
+    /// 2. Sole candidate  - the only field that fits the trait bound.
+    ///
+    /// Anything else is considered a dispute, which we report instead of
+    /// silently picking the first field.
+    pub fn arbitrate(&mut self, error: &Diagnostic) {
+        let sum_returns = self.get_sum_returns();
+
+        let Self {
+            bound,
+            methods,
+            candidates,
+            dispatched,
+            optional,
+            overrides,
+            ..
+        } = self;
+
+        // Group candidates by variant while keeping the variant order.
+        let mut variants: Vec<(&Ident, Vec<&Candidate>)> = vec![];
+
+        for candidate in candidates.iter() {
+            if let Some((_, group)) = variants
+                .iter_mut()
+                .find(|(variant, _)| candidate.variant.eq(*variant))
+            {
+                group.push(candidate);
+            } else {
+                variants.push((&candidate.variant, vec![candidate]));
+            }
+        }
+
+        for (variant, group) in variants {
+            let marked = group
+                .iter()
+                .copied()
+                .filter(|candidate| candidate.marked)
+                .collect::<Vec<_>>();
+
+            let dispatchelor = match (marked.as_slice(), group.as_slice()) {
+                ([candidate], _) | ([], [candidate]) => candidate,
+                // Groups are never empty, but we need to satisfy the matcher.
+                ([], []) => continue,
+                ([], [_, rest @ ..]) => {
+                    rest.iter().for_each(|candidate| {
+                        error.extend_spanned(&candidate.field, ambiguous_dispatch(variant, bound))
+                    });
+                    continue;
+                }
+                ([_, rest @ ..], _) => {
+                    rest.iter().for_each(|candidate| {
+                        error.extend_spanned(
+                            &candidate.field,
+                            conflicting_dispatch_markers(variant, bound),
+                        )
+                    });
+                    continue;
+                }
+            };
+
+            // Variants with the same dispatched type share a sum variant.
+            let sum_variant = dispatched
+                .iter()
+                .find_map(|(variant, ty)| ty.eq(&dispatchelor.field.ty).then_some(variant))
+                .unwrap_or(variant)
+                .clone();
+
+            dispatched.push((variant.clone(), dispatchelor.field.ty.clone()));
+
+            if dispatchelor.optional {
+                optional.push(variant.clone());
+            }
+
+            for (method_name, arm) in dispatchelor.arms.iter() {
+                // Hand-written arms replace the dispatched ones.
+                if overrides
+                    .iter()
+                    .any(|(ident, method, _)| ident == variant && method == method_name)
+                {
+                    continue;
+                }
+
+                let mut arm = arm.clone();
+
+                if let Some(sum_return) = sum_returns.get(method_name) {
+                    sum_return.wrap(&mut arm, &sum_variant);
+                }
+
+                if let Some(arm_vec) = methods.get_mut(method_name) {
+                    arm_vec.push(arm);
+                } else {
+                    methods.insert(method_name.clone(), vec![arm]);
+                }
+            }
+        }
+    }
+
+    /// The impl path in expression position, e.g. `PartialEq::<i32>`.
+    /// `Self` is inferred, as it's the enum and not the dispatched type.
+    fn get_call_path(&self) -> Path {
+        let mut path = self.get_sanatized_impl_path().path;
+        MonomorphizeSelf(&parse_quote!(_)).visit_path_mut(&mut path);
+
+        path.segments.iter_mut().for_each(|segment| {
+            if let PathArguments::AngleBracketed(angle) = &mut segment.arguments {
+                angle.colon2_token = Some(Default::default());
+            }
+        });
+
+        path
+    }
+
+    pub fn get_sanatized_impl_path(&self) -> SynTraitBound {
+        let tb = self.bound.clone();
+        let mut tb: SynTraitBound = parse_quote!(#tb);
+        RemoveBoundBindings.visit_trait_bound_mut(&mut tb);
+        tb
+    }
+
+    /// Used to key blueprints by their normalized trait bound. Generic
+    /// arguments are part of the id, so `Trait<X>` and `Trait<Y>` are
+    /// considered different. Bindings are not, meaning that
+    /// `Deref<Target = str>` and `Deref` share the same id. Generic
+    /// arguments that are left out are filled in with their default, so
+    /// `PartialEq` and `PartialEq<Self>`, the supertrait of `Eq`, share
+    /// the same id too.
+    pub fn get_bound_id(&self) -> UniqueHashId<Type> {
+        let bound = &self.bound;
+        let SynTraitBound { mut path, .. } = parse_quote!(#bound);
+
+        if let Some(segment) = path.segments.last_mut() {
+            let mut args = match &segment.arguments {
+                PathArguments::AngleBracketed(angle) => angle
+                    .args
+                    .iter()
+                    .filter(|arg| !matches!(arg, GenericArgument::Binding(_)))
+                    .cloned()
+                    .collect::<Vec<_>>(),
+                PathArguments::None => vec![],
+                PathArguments::Parenthesized(_) => return UniqueHashId(parse_quote!(#path)),
+            };
+
+            let given = args
+                .iter()
+                .filter(|arg| matches!(arg, GenericArgument::Type(_)))
+                .count();
+
+            args.extend(
+                self.get_schematic_generics()
+                    .skip(given)
+                    .map_while(|gen| gen.default.clone())
+                    .map(GenericArgument::Type),
+            );
+
+            segment.arguments = if args.is_empty() {
+                PathArguments::None
+            } else {
+                PathArguments::AngleBracketed(parse_quote!(<#(#args),*>))
+            };
+        }
+
+        UniqueHashId(parse_quote!(#path))
+    }
+
+    /// See `get_bound_id`. Used when we need the id of a trait bound
+    /// that we don't have a blueprint for.
+    pub fn bound_id(bound: &TraitBound) -> UniqueHashId<Type> {
+        match Blueprint::try_from(Cow::Borrowed(bound)) {
+            Ok(blueprint) => blueprint.get_bound_id(),
+            Err(_) => {
+                let SynTraitBound { mut path, .. } = parse_quote!(#bound);
+                RemoveBoundBindings.visit_path_mut(&mut path);
+                UniqueHashId(parse_quote!(#path))
+            }
+        }
+    }
+
+    /// Used to extract the supertraits that also need to be dispatched
+    /// for our trait to be implemented. Trait generics are substituted
+    /// with the concrete types found in our trait bound, and marker
+    /// traits that the compiler takes care of are skipped.
+    ///
+    /// ```text
+    /// trait IndexMut<Idx>: Index<Idx>
+    ///
+    /// T: ^IndexMut<usize>  =>  T: ^IndexMut<usize> + ^Index<usize>
+    /// ```
+    pub fn get_supertraits(&self) -> Vec<TraitBound> {
+        let polymap = self.get_generics_polymap();
+
+        self.schematic
+            .supertraits
+            .iter()
+            .filter_map(|supertrait| match supertrait {
+                syn::TypeParamBound::Trait(tb) if tb.modifier == syn::TraitBoundModifier::None => {
+                    Some(tb)
+                }
+                _ => None,
+            })
+            .filter(|tb| {
+                !tb.path.segments.last().is_some_and(|segment| {
+                    MARKER_TRAITS.iter().any(|marker| segment.ident.eq(marker))
+                })
+            })
+            .map(|tb| {
+                let mut tb = tb.clone();
+
+                if let Some(polymap) = polymap.as_ref() {
+                    MonomorphizeTraitBound(polymap).visit_trait_bound_mut(&mut tb);
+                }
+
+                let path = &tb.path;
+                let mut bound: TraitBound = parse_quote!(^#path);
+
+                // Pairs of variants should be treated the same way by the
+                // supertraits, e.g. `PartialEq` of `^Ord(mismatch = order)`,
+                // and so should wrapped fields.
+                if let Some((paren, options)) = self.bound.options.as_ref() {
+                    let options = DispatchOptions {
+                        mismatch: options.mismatch,
+                        deref: options.deref.clone(),
+                        ..Default::default()
+                    };
+                    bound.options = Some((*paren, options));
+                }
+
+                bound
+            })
+            .collect()
+    }
+}
+
+/// What a dispatched method returns when there is nothing to dispatch
+/// to, e.g. a unit variant or a wrapped field that is `None`.
+///
+/// Right now, we always default to a panic. But we could consider other
+/// options here too. For example, if we had an Option return type, we
+/// could default with `None` instead. Read more /docs/static-dispatch.md
+fn get_default_return(signature: &Signature) -> TokenStream {
+    match &signature.output {
+        ReturnType::Default => quote::quote!(()),
+        ReturnType::Type(_, ty) => return_default_ret_type(ty).unwrap_or_else(return_panic),
+    }
+}
+
+/// The `Fn*` traits can only be implemented on nightly, so they are
+/// dispatched through inherent methods instead. `^Fn(i32) -> bool`
+/// becomes `call(&self, i32) -> bool`, `call_mut` and `call_once`.
+///
+/// The methods keep their `extern "rust-call"` to tell that the
+/// variants should be called directly, e.g. `Foo::Bar(val) => val(arg0)`.
+fn get_callable_schematic(bound: &TraitBound) -> Option<TraitSchematic> {
+    let Type::Path(TypePath { path, .. }) = &bound.ty else {
+        return None;
+    };
+
+    let PathArguments::Parenthesized(args) = &path.segments.last()?.arguments else {
+        return None;
+    };
+
+    let receivers: &[TokenStream] = match StandardTrait::try_from(path).ok()? {
+        StandardTrait::Fn => &[
+            quote::quote!(call(&self)),
+            quote::quote!(call_mut(&mut self)),
+            quote::quote!(call_once(self)),
+        ],
+        StandardTrait::FnMut => &[
+            quote::quote!(call_mut(&mut self)),
+            quote::quote!(call_once(self)),
+        ],
+        StandardTrait::FnOnce => &[quote::quote!(call_once(self))],
+        _ => return None,
+    };
+
+    let name = bound.get_ident();
+
+    // Elided lifetimes of the arguments would otherwise be bound to
+    // `&self`, so they get a name, e.g. `Fn(&str) -> &str` becomes
+    // `call<'__lt0>(&self, &'__lt0 str) -> &'__lt0 str`.
+    let mut elided = NameElidedLifetimes::default();
+    let mut inputs = args.inputs.iter().cloned().collect::<Vec<_>>();
+
+    inputs.iter_mut().for_each(|ty| elided.visit_type_mut(ty));
+
+    let mut output = match &args.output {
+        ReturnType::Default => parse_quote!(()),
+        ReturnType::Type(_, ty) => ty.as_ref().clone(),
+    };
+
+    if let [lifetime] = elided.lifetimes.as_slice() {
+        ReplaceElidedLifetimes(lifetime).visit_type_mut(&mut output);
+    }
+
+    let lifetimes = elided.names;
+
+    let methods = receivers.iter().map(|receiver| {
+        let mut method: TraitItemMethod = parse_quote!(
+            extern "rust-call" fn #receiver -> #output;
+        );
+
+        method.sig.generics = parse_quote!(<#(#lifetimes),*>);
+
+        for ty in inputs.iter() {
+            method.sig.inputs.push(parse_quote!(_: #ty));
+        }
+
+        method
+    });
+
+    Some(TraitSchematic(parse_quote!(
+        trait #name { #(#methods)* }
+    )))
+}
+
+/// Names the elided lifetimes of the arguments of a callable, see
+/// `get_callable_schematic`. Nested `fn` pointers and `Fn` bounds have
+/// their own elision, so they are left alone.
+#[derive(Default)]
+struct NameElidedLifetimes {
+    /// The names given to the elided lifetimes.
+    names: Vec<Lifetime>,
+
+    /// Every distinct lifetime of the arguments, named or not. The return
+    /// type can only elide its lifetimes if there is exactly one.
+    lifetimes: Vec<Lifetime>,
+}
+
+impl VisitMut for NameElidedLifetimes {
+    fn visit_type_reference_mut(&mut self, node: &mut TypeReference) {
+        if node.lifetime.is_none() {
+            node.lifetime = Some(Lifetime::new("'_", node.and_token.span));
+        }
+
+        visit_type_reference_mut(self, node);
+    }
+
+    fn visit_lifetime_mut(&mut self, node: &mut Lifetime) {
+        if node.ident == "_" {
+            let lifetime = Lifetime::new(&format!("'__lt{}", self.names.len()), node.span());
+            self.names.push(lifetime.clone());
+            *node = lifetime;
+        }
+
+        if !self.lifetimes.contains(node) {
+            self.lifetimes.push(node.clone());
+        }
+    }
+
+    fn visit_type_bare_fn_mut(&mut self, _: &mut syn::TypeBareFn) {}
+
+    fn visit_parenthesized_generic_arguments_mut(
+        &mut self,
+        _: &mut syn::ParenthesizedGenericArguments,
+    ) {
+    }
+}
+
+/// Binds the elided lifetimes of a return type to the only lifetime of
+/// the arguments.
+struct ReplaceElidedLifetimes<'a>(&'a Lifetime);
+
+impl VisitMut for ReplaceElidedLifetimes<'_> {
+    fn visit_type_reference_mut(&mut self, node: &mut TypeReference) {
+        if node.lifetime.is_none() {
+            node.lifetime = Some(self.0.clone());
+        }
+
+        visit_type_reference_mut(self, node);
+    }
+
+    fn visit_lifetime_mut(&mut self, node: &mut Lifetime) {
+        if node.ident == "_" {
+            *node = self.0.clone();
+        }
+    }
+
+    fn visit_type_bare_fn_mut(&mut self, _: &mut syn::TypeBareFn) {}
+
+    fn visit_parenthesized_generic_arguments_mut(
+        &mut self,
+        _: &mut syn::ParenthesizedGenericArguments,
+    ) {
+    }
+}
+
+/// An enum that is generated next to the impl, see
+/// `Blueprint::get_generated_types`.
+pub struct GeneratedType {
+    /// The associated type that is set to the generated type.
+    pub assoc: Option<Ident>,
+
+    /// The associated type or method the type is generated for. Only
+    /// used to report errors.
+    pub source: Ident,
+
+    pub item: ItemEnum,
+
+    /// Traits that are dispatched on the generated type, e.g.
+    /// `Iterator<Item = u32>`.
+    pub bounds: Vec<SynTraitBound>,
+}
+
+impl GeneratedType {
+    /// The bounds to dispatch on the generated type, e.g. `^Iterator<Item = u32>`.
+    /// The `fmt` traits write nothing for the `__Empty` variant, instead
+    /// of panicking when e.g. a unit variant is formatted through
+    /// `-> impl Display`.
+    pub fn get_dispatch_bounds(&self) -> Vec<TokenStream> {
+        self.bounds
+            .iter()
+            .map(|bound| {
+                if is_fmt_trait(&bound.path) {
+                    quote::quote!(^#bound(fallback(fmt = ::core::result::Result::Ok(()))))
+                } else {
+                    quote::quote!(^#bound)
+                }
+            })
+            .collect()
+    }
+}
+
+/// How a method returns one of the generated types, see
+/// `Blueprint::get_generated_types`.
+#[derive(Clone, Debug)]
+enum SumReturn {
+    /// `-> Self::Item`, wrapped as `SourceItem::Lines(val.method())`
+    Direct(Ident),
+
+    /// `-> Option<Self::Item>`, or a `Result`, wrapped as
+    /// `val.method().map(SourceItem::Lines)`
+    Mapped(Ident),
+
+    /// `-> impl Iterator` or `-> Self::IntoIter`, wrapped like `Direct`.
+    /// The fallback arm returns `__Empty` instead of a default.
+    Either(Ident),
+}
+
+impl SumReturn {
+    /// `sum` is the generated type that replaces `assoc`.
+    fn new(output: &ReturnType, assoc: &Ident, sum: Ident) -> Option<Self> {
+        let ReturnType::Type(_, ty) = output else {
+            return None;
+        };
+
+        if is_assoc_type(ty, assoc) {
+            return Some(Self::Direct(sum));
+        }
+
+        let Type::Path(TypePath { qself: None, path }) = ty.as_ref() else {
+            return None;
+        };
+
+        let segment = path.segments.last()?;
+
+        let PathArguments::AngleBracketed(angle) = &segment.arguments else {
+            return None;
+        };
+
+        match (segment.ident.to_string().as_str(), angle.args.first()) {
+            ("Option" | "Result", Some(GenericArgument::Type(ty))) if is_assoc_type(ty, assoc) => {
+                Some(Self::Mapped(sum))
+            }
+            _ => None,
+        }
+    }
+
+    fn into_either(self) -> Self {
+        match self {
+            SumReturn::Direct(sum) => SumReturn::Either(sum),
+            other => other,
+        }
+    }
+
+    fn wrap(&self, arm: &mut Arm, variant: &Ident) {
+        let body = &arm.body;
+
+        *arm.body = match self {
+            SumReturn::Direct(sum) | SumReturn::Either(sum) => parse_quote!(#sum::#variant(#body)),
+            SumReturn::Mapped(sum) => parse_quote!((#body).map(#sum::#variant)),
+        };
+    }
+
+    fn get_fallback(&self) -> Option<TokenStream> {
+        match self {
+            SumReturn::Either(sum) => Some(quote::quote!(#sum::__Empty)),
+            _ => None,
+        }
+    }
+}
+
+/// The trait bounds of `-> impl Trait`, without lifetimes.
+fn get_impl_trait_bounds(output: &ReturnType) -> Option<Punctuated<TypeParamBound, token::Add>> {
+    match output {
+        ReturnType::Type(_, ty) => match ty.as_ref() {
+            Type::ImplTrait(TypeImplTrait { bounds, .. }) => Some(bounds.clone()),
+            _ => None,
+        },
+        ReturnType::Default => None,
+    }
+}
+
+/// Bounds of a generated type live outside of the impl, so `Self` and
+/// `Self::Item` have to be replaced, e.g. `Iterator<Item = Self::Item>`
+/// becomes `Iterator<Item = <Enum as IntoIterator>::Item>` through the
+/// binding of `Item`.
+fn get_either_bounds(
+    bounds: &Punctuated<TypeParamBound, token::Add>,
+    bindings: &[TraitItemType],
+    this: &Type,
+) -> Vec<SynTraitBound> {
+    bounds
+        .iter()
+        .filter_map(|bound| match bound {
+            TypeParamBound::Trait(trait_bound)
+                if matches!(trait_bound.modifier, syn::TraitBoundModifier::None) =>
+            {
+                let mut trait_bound = trait_bound.clone();
+                ReplaceSelfAssoc(bindings).visit_trait_bound_mut(&mut trait_bound);
+                MonomorphizeSelf(this).visit_trait_bound_mut(&mut trait_bound);
+                Some(trait_bound)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// `into_iter` -> `IntoIter`
+fn to_upper_camel(ident: &Ident) -> String {
+    ident
+        .to_string()
+        .split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Replaces `Self::Item` with the type `Item` is bound to.
+struct ReplaceSelfAssoc<'a>(&'a [TraitItemType]);
+
+impl VisitMut for ReplaceSelfAssoc<'_> {
+    fn visit_type_mut(&mut self, node: &mut Type) {
+        let replacement = self.0.iter().find_map(|item| {
+            let is_self_assoc = matches!(node, Type::Path(TypePath { qself: None, path })
+                if path.segments.len() == 2 && path.segments[0].ident == "Self");
+
+            (is_self_assoc && is_assoc_type(node, &item.ident))
+                .then(|| item.default.as_ref().map(|(_, ty)| ty.clone()))
+                .flatten()
+        });
+
+        match replacement {
+            Some(ty) => *node = ty,
+            None => visit_type_mut(self, node),
+        }
+    }
+}
+
+/// `Self::Item` or `Item`
+fn is_assoc_type(ty: &Type, assoc: &Ident) -> bool {
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return false;
+    };
+
+    match path.segments.iter().collect::<Vec<_>>().as_slice() {
+        [segment] => segment.ident.eq(assoc) && segment.arguments.is_empty(),
+        [this, segment] => this.ident == "Self" && segment.ident.eq(assoc),
+        _ => false,
+    }
+}
+
+/// Counts how many times an associated type is used.
+struct FindAssocType<'a>(&'a Ident, usize);
+
+impl<'ast> Visit<'ast> for FindAssocType<'_> {
+    fn visit_type(&mut self, node: &'ast Type) {
+        if is_assoc_type(node, self.0) {
+            self.1 += 1;
+        }
+
+        visit_type(self, node);
+    }
+}
+
+/// Supertraits that are implemented (or checked) by the compiler
+/// itself, and therefore never need to be dispatched.
+const MARKER_TRAITS: [&str; 4] = ["Sized", "Send", "Sync", "Unpin"];
+
+/// Methods of `PartialEq`, `PartialOrd` and `Ord` that compare `self`
+/// to another value.
+fn is_comparison(signature: &Signature) -> bool {
+    matches!(
+        signature.ident.to_string().as_str(),
+        "eq" | "ne" | "lt" | "le" | "gt" | "ge" | "partial_cmp" | "cmp"
+    )
+}
+
+/// The return value of comparison methods, given an `ordering` binding,
+/// e.g. `ordering.is_le()` for `fn le(&self, other: &Self) -> bool`.
+fn get_ordering_return(signature: &Signature) -> Option<TokenStream> {
+    let ReturnType::Type(_, ty) = &signature.output else {
+        return None;
+    };
+
+    let Type::Path(path) = ty.as_ref() else {
+        return None;
+    };
+
+    let ret = path.path.segments.last()?;
+
+    let ordering = match (
+        ret.ident.to_string().as_str(),
+        signature.ident.to_string().as_str(),
+    ) {
+        ("Ordering", _) => quote::quote!(ordering),
+        ("Option", _) => quote::quote!(Some(ordering)),
+        ("bool", "eq") => quote::quote!(ordering.is_eq()),
+        ("bool", "ne") => quote::quote!(ordering.is_ne()),
+        ("bool", "lt") => quote::quote!(ordering.is_lt()),
+        ("bool", "le") => quote::quote!(ordering.is_le()),
+        ("bool", "gt") => quote::quote!(ordering.is_gt()),
+        ("bool", "ge") => quote::quote!(ordering.is_ge()),
+        _ => return None,
+    };
+
+    Some(ordering)
+}
+
+/// Paths that could refer to a std trait, i.e. a bare name, a path into
+/// `std`, `core` or `alloc`, or a path into one of their modules that is
+/// in scope, e.g. `fmt::Write`.
+fn is_std_path(path: &Path) -> bool {
+    const MODULES: [&str; 21] = [
+        "std", "core", "alloc", "any", "borrow", "clone", "cmp", "convert", "default", "error",
+        "fmt", "future", "hash", "io", "iter", "marker", "net", "ops", "process", "slice", "str",
+    ];
+
+    let Some(first) = path.segments.first() else {
+        return false;
+    };
+
+    path.segments.len() == 1 || MODULES.iter().any(|module| first.ident == module)
+}
+
+/// Arguments of type `Self`, `&Self` or `&mut Self`. These have to be
+/// the same variant as `self` to be dispatched, e.g.
+///
+/// ```text
+/// fn eq(&self, other: &Self) -> bool;
+///
+/// (Foo::Bar(val), Foo::Bar(other)) => val.eq(other)
+/// ```
+fn get_pair_params(signature: &Signature) -> Vec<Ident> {
+    signature
+        .inputs
+        .iter()
+        .filter_map(|arg| match arg {
+            FnArg::Typed(PatType { pat, ty, .. }) => {
+                let ty = match ty.as_ref() {
+                    Type::Reference(TypeReference { elem, .. }) => elem,
+                    ty => ty,
+                };
+
+                match pat.as_ref() {
+                    Pat::Ident(pat) if is_self_type(ty) => Some(pat.ident.clone()),
+                    _ => None,
+                }
+            }
+            FnArg::Receiver(_) => None,
+        })
+        .collect()
+}
+
+fn is_self_type(ty: &Type) -> bool {
+    matches!(ty, Type::Path(TypePath { qself: None, path }) if path.is_ident("Self"))
+}
+
+impl<'bound> Blueprint<'bound> {
+    /// This polymap only contains TRAIT GENERIC PARAM MAPPINGS e.g.
+    /// A<i32>
+    fn get_generics_polymap(&self) -> Option<BTreeMap<Ident, &Type>> {
+        let mut types = self.get_bound_generics().into_iter().flatten();
+
+        // Generics that are left out of the trait bound use their
+        // default instead, e.g. `Rhs = Self` in `PartialEq`.
+        let polymap = self
+            .get_schematic_generics()
+            .filter_map(|gen| {
+                types
+                    .next()
+                    .or(gen.default.as_ref())
+                    .map(|ty| (gen.ident.clone(), ty))
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        (!polymap.is_empty()).then_some(polymap)
     }
-}
 
-impl<'bound> Blueprint<'bound> {
     /// Used to extract all bindings in a trait bound
     ///
     /// ```rust
@@ -395,7 +2080,31 @@ impl<'bound> Blueprint<'bound> {
     /// ```
     fn get_schematic_types(&self) -> impl Iterator<Item = TraitItemType> + '_ {
         self.schematic.items.iter().filter_map(|item| match item {
-            TraitItem::Type(ty) => Some(ty.clone()),
+            TraitItem::Type(ty) => {
+                // Bounds on associated types have no effect in impls,
+                // e.g. `type Output: ?Sized;`
+                let mut ty = ty.clone();
+                ty.colon_token = None;
+                ty.bounds.clear();
+                Some(ty)
+            }
+            _ => None,
+        })
+    }
+
+    /// Used to extract all associated consts in a trait
+    ///
+    /// ```rust
+    /// trait Tag {
+    ///     const ID: u32;
+    /// //        ^^
+    /// //        |
+    /// //        Associated const
+    /// }
+    /// ```
+    fn get_schematic_consts(&self) -> impl Iterator<Item = &TraitItemConst> {
+        self.schematic.items.iter().filter_map(|item| match item {
+            TraitItem::Const(item) => Some(item),
             _ => None,
         })
     }
@@ -420,72 +2129,247 @@ impl<'bound> Blueprint<'bound> {
 }
 
 impl<'bound> TryFrom<&'bound TraitBound> for Blueprint<'bound> {
-    type Error = syn::Error;
+    type Error = LookupError;
     fn try_from(bound: &'bound TraitBound) -> Result<Self, Self::Error> {
+        Self::try_from(Cow::Borrowed(bound))
+    }
+}
+
+impl<'bound> TryFrom<Cow<'bound, TraitBound>> for Blueprint<'bound> {
+    type Error = LookupError;
+    /// Only traits that have been tagged in this build are found, so that
+    /// a trait that is tagged after the enum is dispatched once it is,
+    /// instead of dispatching what a previous build stored.
+    fn try_from(bound: Cow<'bound, TraitBound>) -> Result<Self, Self::Error> {
+        Self::lookup(bound, find_live_trait)
+    }
+}
+
+impl<'bound> Blueprint<'bound> {
+    /// Same as `try_from`, but traits that haven't been tagged yet are
+    /// loaded from a previous build as a last resort. Only used for
+    /// bounds that cannot wait for the trait to be tagged, i.e.
+    /// supertraits.
+    pub fn try_from_stored(bound: Cow<'bound, TraitBound>) -> Result<Self, LookupError> {
+        Self::lookup(bound, find_trait)
+    }
+
+    fn lookup(
+        bound: Cow<'bound, TraitBound>,
+        find: fn(&Path) -> Result<ItemTrait, LookupError>,
+    ) -> Result<Self, LookupError> {
         // FIXME: get_ident can be "OMG"
         let b_name = bound.get_ident();
 
-        if let Ok(schematic) = StandardTrait::try_from(&b_name) {
-            Ok(Self {
-                ty: None,
-                schematic: schematic.into(),
-                bound,
-                methods: Default::default(),
-            })
-        } else if let Some(Ok(schematic)) = T_SHM
-            .find(&b_name.to_string())
-            .as_ref()
-            .map(|result| parse_str::<ItemTrait>(result))
-        {
-            Ok(Self {
-                ty: None,
-                schematic: TraitSchematic(schematic),
-                bound,
-                methods: Default::default(),
-            })
+        let mut schematic = if let Some((_, items)) = bound.items.as_ref() {
+            TraitSchematic(parse_quote!(trait #b_name { #(#items)* }))
+        } else if let Type::Path(path) = &bound.ty {
+            // Registered traits shadow std traits with the same name, and
+            // std traits are only found by paths that could refer to them.
+            // Traits stored by a previous build come last, as they might
+            // have been removed since.
+            if !is_std_path(&path.path) {
+                TraitSchematic(find(&path.path)?)
+            } else {
+                match find_live_trait(&path.path) {
+                    Ok(item) => TraitSchematic(item),
+                    Err(LookupError::NotFound) => match StandardTrait::try_from(&path.path) {
+                        Ok(schematic) => schematic.into(),
+                        Err(_) if path.path.is_ident("Write") => {
+                            return Err(LookupError::Ambiguous(vec![
+                                "fmt::Write".to_string(),
+                                "io::Write".to_string(),
+                            ]))
+                        }
+                        Err(_) => TraitSchematic(find(&path.path)?),
+                    },
+                    Err(err) => return Err(err),
+                }
+            }
         } else {
-            Err(syn::Error::new_spanned(bound, trait_not_found(bound)))
+            return Err(LookupError::NotFound);
+        };
+
+        if let Some(callable) = get_callable_schematic(&bound) {
+            schematic = callable;
         }
-    }
-}
 
-fn trait_not_found(bound: &TraitBound) -> String {
-    format!("`{}` cannot be found. Make sure the trait is tagged with the `#[penum]` attribute, and is invoked before your enum.", bound.get_ident())
+        let params = schematic
+            .0
+            .items
+            .iter_mut()
+            .filter_map(|item| match item {
+                TraitItem::Method(method) => {
+                    Some((method.sig.ident.clone(), rebind_params(&mut method.sig)))
+                }
+                _ => None,
+            })
+            .collect();
+
+        Ok(Self {
+            ty: None,
+            schematic,
+            bound,
+            methods: Default::default(),
+            candidates: Default::default(),
+            units: Default::default(),
+            variants: Default::default(),
+            dispatched: Default::default(),
+            optional: Default::default(),
+            params,
+            overrides: Default::default(),
+        })
+    }
 }
 
 impl<'bound> BlueprintsMap<'bound> {
-    /// This flattens values in the map.
-    /// ty: [blueprint] -> [[blueprint]] -> [blueprint]
+    /// Register a blueprint for a bounded type. If another bounded type
+    /// has already registered the same trait bound, we reuse that
+    /// blueprint instead so that both end up in the same impl.
     ///
-    /// NOTE: Some of these blueprints might be duplicates, meaning that we implement two or more
-    /// times for the same trait. So what we have to do here is deduplicate the blueprints by
-    /// mapping over the trait bounds instead of the concrete types.
+    /// Returns false if the bounded type already had the trait bound.
+    pub fn insert(&mut self, bounded_ty: &Type, blueprint: Blueprint<'bound>) -> bool {
+        let bound_id = blueprint.get_bound_id();
+
+        let inserted = self
+            .bounded
+            .entry(UniqueHashId::new(bounded_ty))
+            .or_default()
+            .insert(bound_id.clone());
+
+        self.blueprints.entry(bound_id).or_insert(blueprint);
+
+        inserted
+    }
+
+    /// Register blueprints for the supertraits of a dispatched trait.
+    /// Supertraits that have already been registered for the bounded
+    /// type, explicitly or through another trait, are skipped.
     ///
-    /// FIXME: Change so that we can map on trait bounds instead of just concrete types. Each
-    /// implementation needs to be unique, i.e. there can only be one trait implementation per type.
-    /// Note, Trait<U> and Trait<T> are considered different, so we should support generic traits.
-    pub fn for_each_blueprint(&self, mut f: impl FnMut(&Blueprint)) {
-        // TODO: We could probably just use a HashSet instead and implement Hash for Blueprint->bound.
-        let mut deduplicates: BTreeMap<UniqueHashId<Type>, Blueprint<'bound>> = Default::default();
-
-        for item in self.0.iter() {
-            for blueprint in item.1.iter() {
-                let bound = blueprint.bound;
-                let id: Type = parse_quote!(#bound);
-                let id_unique = UniqueHashId::new(&id);
-
-                // FIXME: TEMP, should fix this copy mess
-                if let Some(unique_entry) = deduplicates.get_mut(&id_unique) {
-                    unique_entry
-                        .methods
-                        .extend(blueprint.methods.clone().into_iter());
-                } else {
-                    deduplicates.insert(id_unique, blueprint.clone());
+    /// Should be called after all explicit bounds have been inserted so
+    /// that they take precedence over derived ones.
+    pub fn insert_supertraits(
+        &mut self,
+        bounded_ty: &Type,
+        subtrait: &TraitBound,
+        supertraits: Vec<TraitBound>,
+        error: &Diagnostic,
+    ) {
+        for supertrait in supertraits {
+            let blueprint = match Blueprint::try_from_stored(Cow::Owned(supertrait.clone())) {
+                Ok(blueprint) => blueprint,
+                Err(LookupError::Ambiguous(candidates)) => {
+                    error.extend_spanned(
+                        subtrait,
+                        ambiguous_trait(&supertrait.get_ident(), &candidates),
+                    );
+                    continue;
                 }
+                Err(LookupError::Unqualified) => {
+                    error.extend_spanned(subtrait, unqualified_trait(&supertrait.get_ident()));
+                    continue;
+                }
+                Err(LookupError::NotFound) => {
+                    error.extend_spanned(
+                        subtrait,
+                        supertrait_not_found(&supertrait.get_ident(), &subtrait.get_ident()),
+                    );
+                    continue;
+                }
+            };
+
+            if let Some(method) = blueprint.get_pinned_deref() {
+                error.extend_spanned(
+                    subtrait,
+                    pinned_deref_not_permitted(&method, &supertrait.get_ident()),
+                );
+                continue;
+            }
+
+            let supertraits = blueprint.get_supertraits();
+            let bound_id = blueprint.get_bound_id();
+
+            if self.insert(bounded_ty, blueprint) {
+                self.insert_supertraits(bounded_ty, subtrait, supertraits, error);
+            } else if let Some(blueprint) = self.blueprints.get_mut(&bound_id) {
+                blueprint.inherit_options(&supertrait);
+            }
+        }
+    }
+
+    /// Postpone a dispatched trait that cannot be found yet.
+    pub fn defer(&mut self, bound: &TraitBound) {
+        self.pending.push(bound.clone());
+    }
+
+    pub fn get_pending(&self) -> &[TraitBound] {
+        &self.pending
+    }
+
+    /// Import a dispatched trait from another crate, see `import_trait`.
+    pub fn import(&mut self, bound: &TraitBound) {
+        self.foreign.push(bound.clone());
+    }
+
+    pub fn get_foreign(&self) -> &[TraitBound] {
+        &self.foreign
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blueprints.is_empty() && self.pending.is_empty() && self.foreign.is_empty()
+    }
+
+    /// Decide which candidate to dispatch for each variant, see
+    /// `Blueprint::arbitrate`.
+    pub fn arbitrate(&mut self, error: &Diagnostic) {
+        self.blueprints
+            .values_mut()
+            .for_each(|blueprint| blueprint.arbitrate(error))
+    }
+
+    /// Each blueprint is unique per trait bound, i.e. there can only be
+    /// one trait implementation per enum.
+    pub fn for_each_blueprint(&self, f: impl FnMut(&Blueprint)) {
+        self.blueprints.values().for_each(f)
+    }
+
+    /// Hands a `#[penum(arm(..))]` to the blueprints of its trait. Returns
+    /// false when the trait isn't dispatched.
+    pub fn attach_arm_override(
+        &mut self,
+        variant: &Ident,
+        arm: &ArmOverride,
+        error: &Diagnostic,
+    ) -> bool {
+        let Some(name) = arm.trait_path.segments.last().map(|segment| &segment.ident) else {
+            return false;
+        };
+
+        let mut found = false;
+
+        for blueprint in self.blueprints.values_mut() {
+            let trait_name = blueprint.bound.get_ident();
+
+            if trait_name.ne(name) {
+                continue;
+            }
+
+            found = true;
+
+            if blueprint
+                .get_schematic_methods()
+                .all(|method| method.sig.ident.ne(&arm.method))
+            {
+                error.extend_spanned(&arm.method, unknown_arm_method(&arm.method, &trait_name));
+                continue;
             }
+
+            blueprint
+                .overrides
+                .push((variant.clone(), arm.method.clone(), arm.expr.clone()));
         }
 
-        deduplicates.iter().for_each(|m| f(m.1))
+        found
     }
 
     pub fn find_and_attach(
@@ -494,20 +2378,40 @@ impl<'bound> BlueprintsMap<'bound> {
         variant_sig: &VariantSig,
         ty: Option<&Type>,
     ) -> bool {
-        if let Some(bp_list) = self.get_mut(id) {
-            for blueprint in bp_list.iter_mut() {
-                blueprint.attach(variant_sig);
-
-                // This will ensure that we only select the first ty.
-                if ty.is_some() && blueprint.ty.is_none() {
-                    // Ouff, a lot of copying. Maybe use a reference?
-                    blueprint.ty = Some(Box::from(unsafe { ty.unwrap_unchecked() }.clone()))
-                }
+        let Some(bound_ids) = self.bounded.get(id) else {
+            return false;
+        };
+
+        for bound_id in bound_ids.iter() {
+            let Some(blueprint) = self.blueprints.get_mut(bound_id) else {
+                continue;
+            };
+
+            blueprint.attach(variant_sig);
+
+            // This will ensure that we only select the first ty.
+            if let (Some(ty), None) = (ty, blueprint.ty.as_ref()) {
+                let (_, ty) = blueprint.bound.get_deref().unwrap(ty);
+                blueprint.ty = Some(Box::new(ty.clone()))
             }
-            true
-        } else {
-            false
         }
+
+        true
+    }
+
+    /// Should be called with every variant of the enum, in order.
+    pub fn attach_variant(&mut self, variant: &Ident) {
+        self.blueprints
+            .values_mut()
+            .for_each(|blueprint| blueprint.variants.push(variant.clone()));
+    }
+
+    /// Unit variants have nothing to dispatch, but they are still
+    /// needed to rebuild methods that return `Self`.
+    pub fn attach_unit_variant(&mut self, variant: &Ident) {
+        self.blueprints
+            .values_mut()
+            .for_each(|blueprint| blueprint.units.push(variant.clone()));
     }
 
     pub fn find_and_attach_variant_sig(
@@ -516,33 +2420,22 @@ impl<'bound> BlueprintsMap<'bound> {
         variant_ident: &Ident,
         field_item: &syn::Field,
         field_index: usize,
-        arity: usize,
+        fields: &syn::Fields,
         item_ty_unique: &UniqueHashId<Type>,
     ) {
         let variant_sig =
-            VariantSig::new(enum_ident, variant_ident, field_item, field_index, arity);
+            VariantSig::new(enum_ident, variant_ident, field_item, field_index, fields);
 
         self.find_and_attach(item_ty_unique, &variant_sig, Some(item_ty_unique));
     }
 }
 
-impl<'bound> Deref for BlueprintsMap<'bound> {
-    type Target = BTreeMap<UniqueHashId<Type>, Vec<Blueprint<'bound>>>;
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-
-impl<'bound> DerefMut for BlueprintsMap<'bound> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        self.0.borrow_mut()
-    }
-}
-
 impl VisitMut for MonomorphizeFnSignature<'_> {
-    /// Skip mutating generic parameter in method signature
-    fn visit_generics_mut(&mut self, _: &mut syn::Generics) {}
+    /// Method generics are kept, but their bounds are monomorphized too,
+    /// e.g. `T: IntoIterator<Item = A>` in `Extend<A>::extend<T>`.
+    fn visit_generics_mut(&mut self, node: &mut syn::Generics) {
+        visit_generics_mut(self, node);
+    }
 
     /// We only care about mutating path types
     fn visit_type_mut(&mut self, node: &mut syn::Type) {
@@ -573,35 +2466,32 @@ impl VisitMut for MonomorphizeTraitBound<'_> {
 }
 
 impl VisitMut for RemoveBoundBindings {
-    fn visit_angle_bracketed_generic_arguments_mut(
-        &mut self,
-        node: &mut syn::AngleBracketedGenericArguments,
-    ) {
-        let mut rep_gas: Punctuated<GenericArgument, Token![,]> = Default::default();
-
-        let mut args = node.args.iter().peekable();
-
-        // Ugh, refactor this
-        loop {
-            let (Some(gen), s) = (args.next(), args.peek()) else {
-                break;
-            };
-
-            if !matches!(gen, GenericArgument::Binding(_)) {
-                rep_gas.push_value(gen.clone());
-
-                if let Some(GenericArgument::Binding(_)) = s {
-                    break;
-                } else {
-                    rep_gas.push_punct(parse_quote!(,));
-                }
+    fn visit_path_arguments_mut(&mut self, node: &mut PathArguments) {
+        if let PathArguments::AngleBracketed(angle) = node {
+            let args = angle
+                .args
+                .iter()
+                .filter(|arg| !matches!(arg, GenericArgument::Binding(_)))
+                .collect::<Vec<_>>();
+
+            // `Neg<Output = Self>` becomes `Neg`, not `Neg<>`.
+            *node = if args.is_empty() {
+                PathArguments::None
+            } else {
+                PathArguments::AngleBracketed(parse_quote!(<#(#args),*>))
             };
         }
 
-        if args.count() != 0 {
-            node.args = rep_gas;
+        visit_path_arguments_mut(self, node);
+    }
+}
+
+impl VisitMut for MonomorphizeSelf<'_> {
+    fn visit_type_mut(&mut self, node: &mut Type) {
+        if is_self_type(node) {
+            *node = self.0.clone();
         }
 
-        visit_angle_bracketed_generic_arguments_mut(self, node);
+        visit_type_mut(self, node);
     }
 }
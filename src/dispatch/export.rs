@@ -0,0 +1,223 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeSet;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use syn::braced;
+use syn::bracketed;
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::parse_quote;
+use syn::punctuated::Punctuated;
+use syn::Ident;
+use syn::ItemTrait;
+use syn::LitStr;
+use syn::Token;
+use syn::Type;
+use syn::TypeParamBound;
+
+use crate::factory::TraitBound;
+use crate::utils::Stringify;
+use crate::utils::EXPORT_MACRO_PREFIX;
+
+use super::registry::find_trait;
+use super::standard::StandardTrait;
+
+/// Contains everything we need to resume the dispatch of a trait that
+/// lives in another crate. It's what the exported macro of the trait
+/// passes back to `penum::__dispatch`.
+///
+/// ```text
+/// [other_crate::Trait] [(T) where T: ^other_crate::Trait] { enum Foo { .. } } ["AsRef<str>"] trait Trait { .. }
+///  ^^^^^^^^^^^^^^^^^^   ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^   ^^^^^^^^^^^^^^^^^   ^^^^^^^^^^^^   ^^^^^^^^^^^^^^^^^
+///  |                    |                                   |                   |              |
+///  The dispatched bound |                                   The enum            |              Appended by the exported macro
+///                       The penum expression                                    Already implemented trait bounds
+/// ```
+pub struct ForeignDispatch {
+    pub ty: Type,
+    pub expr: TokenStream,
+    pub subject: TokenStream,
+    pub dispatched: BTreeSet<String>,
+    pub schematic: ItemTrait,
+
+    /// Supertraits that were registered in the other crate, so that we
+    /// can dispatch them together with the trait.
+    pub supertraits: Vec<ItemTrait>,
+}
+
+/// Exports the trait so that enums in other crates can dispatch it with
+/// `^other_crate::module::Trait`. Calling the macro with a callback will
+/// invoke the callback with the trait appended to the payload.
+///
+/// ```text
+/// other_crate::module::__penum_Trait! { (::penum::__dispatch) <payload> }
+///
+/// ::penum::__dispatch! { <payload> trait Trait { .. } }
+/// ```
+///
+/// Exported macros all live in the root of the crate, so the macro gets
+/// a unique name that is re-exported next to the trait. That way, traits
+/// with the same name in different modules don't collide.
+pub fn export_trait(item_trait: &ItemTrait) -> TokenStream {
+    let name = get_export_name(&item_trait.ident);
+    let unique = get_unique_export_name(item_trait);
+    let supertraits = get_registered_supertraits(item_trait);
+
+    quote!(
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! #unique {
+            (($($callback:tt)*) $($payload:tt)*) => {
+                $($callback)*! { $($payload)* #item_trait #(#supertraits)* }
+            };
+        }
+
+        #[doc(hidden)]
+        #[allow(unused_imports)]
+        pub use #unique as #name;
+    )
+}
+
+/// Used to find the supertraits, and their supertraits, that have been
+/// tagged with `#[penum]` before the trait.
+fn get_registered_supertraits(item_trait: &ItemTrait) -> Vec<ItemTrait> {
+    let mut found: Vec<ItemTrait> = vec![];
+    let mut queue = vec![item_trait.clone()];
+
+    while let Some(item) = queue.pop() {
+        for bound in item.supertraits.iter() {
+            let TypeParamBound::Trait(tb) = bound else {
+                continue;
+            };
+
+            let Some(ident) = tb.path.segments.last().map(|segment| &segment.ident) else {
+                continue;
+            };
+
+            if item_trait.ident.eq(ident) || found.iter().any(|t| t.ident.eq(ident)) {
+                continue;
+            }
+
+            if let Ok(schematic) = find_trait(&tb.path) {
+                queue.push(schematic.clone());
+                found.push(schematic);
+            }
+        }
+    }
+
+    found
+}
+
+/// Supertrait paths are written relative to the crate that exported the
+/// trait. We assume that they are relative to its root, e.g.
+/// `inner::Speak` becomes `other_crate::inner::Speak`.
+pub fn qualify_supertraits(schematic: &mut ItemTrait, krate: &Ident) {
+    for bound in schematic.supertraits.iter_mut() {
+        let TypeParamBound::Trait(tb) = bound else {
+            continue;
+        };
+
+        let path = &mut tb.path;
+        let is_ident = path.segments.len() == 1;
+
+        if path.leading_colon.is_some() {
+            continue;
+        }
+
+        let Some(first) = path.segments.first_mut() else {
+            continue;
+        };
+
+        match first.ident.to_string().as_str() {
+            "crate" => first.ident = krate.clone(),
+            "std" | "core" | "alloc" | "self" | "super" => continue,
+            _ if is_ident && StandardTrait::try_from(&first.ident).is_ok() => continue,
+            _ => *path = parse_quote!(#krate::#path),
+        }
+    }
+}
+
+/// Calls the macro that was exported together with a trait in another
+/// crate, see `export_trait`.
+pub fn import_trait(
+    bound: &TraitBound,
+    expr: &TokenStream,
+    subject: &TokenStream,
+    dispatched: &BTreeSet<String>,
+) -> TokenStream {
+    let (Some(krate), Type::Path(path)) = (bound.get_crate_ident(), &bound.ty) else {
+        return TokenStream::new();
+    };
+
+    let leading_colon = &path.path.leading_colon;
+    let segments = &path.path.segments;
+    let module = segments
+        .iter()
+        .skip(1)
+        .take(segments.len().saturating_sub(2));
+    let name = get_export_name(&bound.get_ident());
+    let ty = &bound.ty;
+
+    quote!(
+        #leading_colon #krate #(::#module)*::#name! {
+            (::penum::__dispatch) [#ty] [#expr] { #subject } [#(#dispatched),*]
+        }
+    )
+}
+
+/// `__penum_` followed by the name of the trait.
+fn get_export_name(ident: &Ident) -> Ident {
+    format_ident!("{}{}", EXPORT_MACRO_PREFIX, ident)
+}
+
+/// The name of the trait followed by a hash of its definition and where
+/// it's defined, e.g. `__penum_Trait_5f1c..`.
+fn get_unique_export_name(item_trait: &ItemTrait) -> Ident {
+    let mut hasher = DefaultHasher::new();
+
+    item_trait.get_string().hash(&mut hasher);
+    format!("{:?}", item_trait.ident.span()).hash(&mut hasher);
+
+    format_ident!(
+        "{}{}_{:x}",
+        EXPORT_MACRO_PREFIX,
+        item_trait.ident,
+        hasher.finish()
+    )
+}
+
+impl Parse for ForeignDispatch {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty;
+        let expr;
+        let subject;
+        let dispatched;
+
+        bracketed!(ty in input);
+        bracketed!(expr in input);
+        braced!(subject in input);
+        bracketed!(dispatched in input);
+
+        Ok(Self {
+            ty: ty.parse()?,
+            expr: expr.parse()?,
+            subject: subject.parse()?,
+            dispatched: Punctuated::<LitStr, Token![,]>::parse_terminated(&dispatched)?
+                .iter()
+                .map(LitStr::value)
+                .collect(),
+            schematic: input.parse()?,
+            supertraits: {
+                let mut supertraits = vec![];
+                while !input.is_empty() {
+                    supertraits.push(input.parse()?);
+                }
+                supertraits
+            },
+        })
+    }
+}
@@ -1,12 +1,66 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
 pub use self::blueprint::Blueprint;
 pub use self::blueprint::BlueprintsMap;
+pub use self::blueprint::GeneratedType;
+pub use self::blueprint::MonomorphizeSelf;
+pub use self::export::export_trait;
+pub use self::export::import_trait;
+pub use self::export::qualify_supertraits;
+pub use self::export::ForeignDispatch;
+pub use self::pending::trait_not_found_guards;
+pub use self::pin::pin_projection_guards;
+pub use self::registry::register_trait;
+pub use self::registry::register_trait_as;
+pub use self::registry::LookupError;
+pub use self::registry::TraitAttr;
+pub use self::ret::register_fallback;
 pub use self::sig::VariantSig;
 
 mod blueprint;
+mod export;
+mod pending;
+mod pin;
+mod registry;
 mod ret;
 mod shm;
 mod sig;
 mod standard;
 
+/// Registered traits, keyed by their path, e.g. `net::Handler`. See `registry`.
+///
 /// Storing token streams will cause "use after free" error, so we store them as Strings instead.
 pub static T_SHM: self::shm::SharedMemory<String, String> = self::shm::SharedMemory::new();
+
+/// Every definition of the traits that were registered without a path,
+/// keyed by their name. See `registry::find_trait`.
+pub static B_SHM: self::shm::SharedMemory<String, BTreeSet<String>> =
+    self::shm::SharedMemory::new();
+
+/// Traits that were loaded from the registry of a previous build, keyed
+/// like `T_SHM`. Used to tell if the trait changed after it was dispatched.
+pub static P_SHM: self::shm::SharedMemory<String, String> = self::shm::SharedMemory::new();
+
+/// Consts registered with `#[penum::fallback]`, keyed by their module and
+/// type, e.g. `model::Status -> crate::model::STATUS_DEFAULT`. See `ret`.
+pub static F_SHM: self::shm::SharedMemory<String, String> = self::shm::SharedMemory::new();
+
+/// Dispatch requests from enums that were expanded before the trait they
+/// dispatch had been registered. Keyed by the enum definition.
+pub static D_SHM: self::shm::SharedMemory<String, PendingDispatch> = self::shm::SharedMemory::new();
+
+/// A dispatch request that is resumed once all the traits it is waiting
+/// on have been tagged with `#[penum]`.
+#[derive(Clone, Debug)]
+pub struct PendingDispatch {
+    /// The penum expression, e.g. `(T) where T: ^Trait`
+    pub expr: String,
+
+    /// Names of the traits that haven't been registered yet, and the
+    /// paths that the enum refers to them with, e.g. `net::Handler`.
+    pub waiting: BTreeMap<String, String>,
+
+    /// Trait bounds that have already been implemented for the enum.
+    pub dispatched: BTreeSet<String>,
+}
@@ -0,0 +1,69 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use quote::quote_spanned;
+use syn::parse_quote;
+use syn::spanned::Spanned;
+use syn::Generics;
+
+use crate::factory::Subject;
+use crate::factory::TraitBound;
+use crate::utils::trait_not_found;
+
+/// Dispatched traits that cannot be found are postponed, as they might
+/// get tagged with `#[penum]` later on, see `resume_pending_dispatch`.
+/// We cannot tell when the crate has been fully expanded, so we leave it
+/// to the compiler to check that the trait was implemented in the end.
+///
+/// ```text
+/// impl<T: ?Sized + Trait> TraitNotFound for T {}
+///
+/// fn check() {
+///     <Enum as TraitNotFound>::found();
+/// }
+/// ```
+///
+/// The marker trait is generic over the same parameters as the enum, so
+/// that the bound can refer to them, e.g. `^AsRef<T>`.
+pub fn trait_not_found_guards(subject: &Subject, pending: &[TraitBound]) -> TokenStream {
+    let Subject {
+        ident, generics, ..
+    } = subject;
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    pending
+        .iter()
+        .map(|bound| {
+            let path = &bound.ty;
+            let message = trait_not_found(&bound.get_ident());
+
+            let mut dispatched_generics: Generics = generics.clone();
+            dispatched_generics
+                .params
+                .push(parse_quote!(__Dispatched: ?Sized + #path));
+
+            let (dispatched_impl_generics, ..) = dispatched_generics.split_for_impl();
+
+            let check = quote_spanned!(path.span()=>
+                <#ident #ty_generics as TraitNotFound #ty_generics>::found();
+            );
+
+            quote!(
+                const _: () = {
+                    #[diagnostic::on_unimplemented(message = #message)]
+                    trait TraitNotFound #impl_generics #where_clause {
+                        fn found() {}
+                    }
+
+                    impl #dispatched_impl_generics TraitNotFound #ty_generics for __Dispatched
+                        #where_clause {}
+
+                    #[allow(dead_code)]
+                    fn check #impl_generics () #where_clause {
+                        #check
+                    }
+                };
+            )
+        })
+        .collect()
+}
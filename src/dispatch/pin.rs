@@ -0,0 +1,76 @@
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use syn::parse_quote;
+use syn::Generics;
+use syn::Type;
+
+use crate::factory::Subject;
+
+/// Methods taking `self: Pin<&mut Self>`, e.g. `Future::poll`, are
+/// dispatched by projecting the pin onto the dispatched field.
+///
+/// ```text
+/// match unsafe { Pin::get_unchecked_mut(self) } {
+///     Job::A(val) => (unsafe { Pin::new_unchecked(val) }).poll(cx),
+/// }
+/// ```
+///
+/// This kind of structural pinning is only sound if the enum upholds
+/// the same guarantees as the pinned fields, so we make sure that:
+///
+/// 1. The enum is only `Unpin` when all of its pinned fields are. We
+///    implement `Unpin` ourselves, which also stops the user from
+///    implementing it.
+/// 2. The enum doesn't implement `Drop`, as `drop` takes `&mut self`
+///    and could move the fields out. Implementing it will cause a
+///    conflicting implementation of `MustNotImplDrop`.
+///
+/// Fields that aren't dispatched are never pinned, and don't affect
+/// whether the enum is `Unpin`.
+///
+/// Everything is scoped in a `const _` so that none of it can be named
+/// by the user. The `'__pin` lifetime keeps the `Unpin` bound from
+/// being trivially false for concrete types.
+pub fn pin_projection_guards(subject: &Subject, pinned: &[Type]) -> TokenStream {
+    let Subject {
+        ident, generics, ..
+    } = subject;
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let mut pin_generics: Generics = generics.clone();
+    pin_generics.params.insert(0, parse_quote!('__pin));
+
+    let (pin_impl_generics, pin_ty_generics, pin_where_clause) = pin_generics.split_for_impl();
+
+    let mut unpin_generics = pin_generics.clone();
+    unpin_generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(__Pinned #pin_ty_generics: ::core::marker::Unpin));
+
+    let unpin_where_clause = &unpin_generics.where_clause;
+    let fields = (0..pinned.len()).map(|index| format_ident!("__field{}", index));
+
+    quote!(
+        const _: () = {
+            #[doc(hidden)]
+            #[allow(dead_code)]
+            pub struct __Pinned #pin_impl_generics #pin_where_clause {
+                __pin: ::core::marker::PhantomData<&'__pin ()>,
+                __enum: ::core::marker::PhantomData<fn() -> #ident #ty_generics>,
+                #(#fields: #pinned),*
+            }
+
+            impl #pin_impl_generics ::core::marker::Unpin for #ident #ty_generics #unpin_where_clause {}
+
+            trait MustNotImplDrop {}
+
+            #[allow(drop_bounds)]
+            impl<T: ::core::ops::Drop> MustNotImplDrop for T {}
+
+            impl #impl_generics MustNotImplDrop for #ident #ty_generics #where_clause {}
+        };
+    )
+}
@@ -0,0 +1,446 @@
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::parse_str;
+use syn::Ident;
+use syn::ItemTrait;
+use syn::Path;
+use syn::Token;
+
+use crate::utils::Stringify;
+
+use super::B_SHM;
+use super::P_SHM;
+use super::T_SHM;
+
+mod store;
+
+/// Why a trait bound couldn't be resolved to a registered trait.
+#[derive(Debug)]
+pub enum LookupError {
+    /// The trait hasn't been registered (yet).
+    NotFound,
+
+    /// More than one registered trait matches the bound.
+    Ambiguous(Vec<String>),
+
+    /// Traits with the same name were registered without a path, so we
+    /// can't tell which one the bound refers to.
+    Unqualified,
+}
+
+/// The arguments of `#[penum]` when it's used on a trait.
+///
+/// ```text
+/// #[penum(path = net)]
+/// trait Handler {}        // Registered as `net::Handler`
+/// ```
+#[derive(Default)]
+pub struct TraitAttr {
+    pub path: Option<Path>,
+}
+
+/// Registers a trait under its path, e.g. `net::Handler`, or only its
+/// name if the path is unknown. The trait is also persisted, see
+/// `store`, and stored traits with the same name that haven't been
+/// registered are removed, as the trait has probably moved.
+///
+/// Returns false if a different definition of the trait, loaded from a
+/// previous build, has already been dispatched as a supertrait.
+pub fn register_trait(module: Option<&Path>, item_trait: &ItemTrait) -> bool {
+    let mut key = module.map(get_key).unwrap_or_default();
+
+    if !key.is_empty() {
+        key.push_str("::");
+    }
+
+    let ident = item_trait.ident.to_string();
+    key.push_str(&ident);
+
+    let schematic = item_trait.get_string();
+    let live = T_SHM.find_all(|other| get_name(other) == ident);
+
+    store::remove_where(|other| {
+        get_name(other) == ident && other != &key && live.iter().all(|(k, _)| k != other)
+    });
+    store::save(&key, &schematic);
+
+    // Traits without a path are registered under their name, so we keep
+    // every definition to tell if they would overwrite each other.
+    if module.is_none() {
+        let mut bare = B_SHM.find(&key).unwrap_or_default();
+        bare.insert(schematic.clone());
+        B_SHM.insert(key.clone(), bare);
+    }
+
+    let outdated = P_SHM.find(&key).is_some_and(|loaded| loaded != schematic);
+    T_SHM.insert(key, schematic);
+
+    !outdated
+}
+
+/// Registers a trait under the path that it was dispatched with.
+pub fn register_trait_as(path: &Path, item_trait: &ItemTrait) {
+    T_SHM.insert(get_key(path), item_trait.get_string());
+}
+
+/// Finds the registered trait that a trait bound refers to. The path of
+/// the bound has to match the end of the registered path, though a
+/// trait that was registered without a path matches any path with the
+/// same name, unless another trait was registered with that name.
+///
+/// ```text
+/// ^net::Handler  =>  net::Handler, app::net::Handler or Handler
+/// ^Handler       =>  Handler, net::Handler or http::Handler, but only one of them.
+/// ```
+///
+/// Traits that haven't been tagged in this build are loaded from the
+/// store, see `Blueprint::try_from_stored`.
+pub fn find_trait(path: &Path) -> Result<ItemTrait, LookupError> {
+    lookup(path, true)
+}
+
+/// Same as `find_trait`, but only for traits that have been registered
+/// by this build.
+pub fn find_live_trait(path: &Path) -> Result<ItemTrait, LookupError> {
+    lookup(path, false)
+}
+
+fn lookup(path: &Path, load: bool) -> Result<ItemTrait, LookupError> {
+    let key = get_key(path);
+    let segments = key.split("::").collect::<Vec<_>>();
+    let Some(ident) = segments.last() else {
+        return Err(LookupError::NotFound);
+    };
+
+    let is_candidate = |key: &String| get_name(key) == *ident;
+    let mut candidates = T_SHM.find_all(is_candidate);
+
+    // Traits that haven't been tagged yet in this build might have been
+    // registered by a previous one.
+    let loaded = load && candidates.is_empty();
+    if loaded {
+        candidates = store::load_where(is_candidate);
+    }
+
+    let matching = candidates
+        .iter()
+        .filter(|(key, _)| key.split("::").collect::<Vec<_>>().ends_with(&segments))
+        .collect::<Vec<_>>();
+
+    let (key, found) = match (matching.as_slice(), candidates.as_slice()) {
+        ([found], _) => found,
+        ([], [found]) if !found.0.contains("::") => found,
+        ([], _) => return Err(LookupError::NotFound),
+        _ => {
+            return Err(LookupError::Ambiguous(
+                matching.into_iter().map(|(key, _)| key.clone()).collect(),
+            ))
+        }
+    };
+
+    if !key.contains("::") && B_SHM.find(key).is_some_and(|bare| bare.len() > 1) {
+        return Err(LookupError::Unqualified);
+    }
+
+    // Remember what we've dispatched, so that we can tell if the trait
+    // turns out to have changed since.
+    if loaded {
+        P_SHM.insert(key.clone(), found.clone());
+    }
+
+    parse_str(found).map_err(|_| LookupError::NotFound)
+}
+
+/// The name of the trait, i.e. the last segment of a key.
+fn get_name(key: &str) -> &str {
+    key.rsplit("::").next().unwrap_or(key)
+}
+
+/// Used as registry key. Generic arguments, leading colons and
+/// `crate`, `self` and `super` segments are left out.
+fn get_key(path: &Path) -> String {
+    path.segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .skip_while(|ident| matches!(ident.as_str(), "crate" | "self" | "super"))
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+impl Parse for TraitAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.is_empty() {
+            return Ok(Self::default());
+        }
+
+        let ident: Ident = input.parse()?;
+
+        if ident != "path" {
+            return Err(syn::Error::new(
+                ident.span(),
+                "Expected `path = ..`, the module the item is in.",
+            ));
+        }
+
+        let _: Token![=] = input.parse()?;
+
+        Ok(Self {
+            path: Some(input.parse()?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+
+    use syn::{parse_quote, ItemTrait, Path};
+
+    use crate::utils::Stringify;
+
+    use super::store;
+    use super::{find_live_trait, find_trait, register_trait, LookupError};
+
+    fn register(module: Option<Path>, item_trait: ItemTrait) -> bool {
+        register_trait(module.as_ref(), &item_trait)
+    }
+
+    /// Stores the trait the same way a previous build would have.
+    fn save(key: &str, item_trait: ItemTrait) {
+        store::save(key, &item_trait.get_string());
+    }
+
+    fn store_dir(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("penum-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn find_path_qualified_trait() {
+        register(
+            Some(parse_quote!(net)),
+            parse_quote!(
+                trait RegHandler {
+                    fn net(&self);
+                }
+            ),
+        );
+        register(
+            Some(parse_quote!(http)),
+            parse_quote!(
+                trait RegHandler {
+                    fn http(&self);
+                }
+            ),
+        );
+
+        let found = find_trait(&parse_quote!(crate::net::RegHandler)).unwrap();
+        assert!(found.items.len() == 1 && found.ident == "RegHandler");
+
+        let expect: ItemTrait = parse_quote!(
+            trait RegHandler {
+                fn http(&self);
+            }
+        );
+        let found = find_trait(&parse_quote!(http::RegHandler)).unwrap();
+        assert_eq!(found, expect);
+
+        assert!(matches!(
+            find_trait(&parse_quote!(ftp::RegHandler)),
+            Err(LookupError::NotFound)
+        ));
+    }
+
+    #[test]
+    fn ambiguous_bare_trait() {
+        register(
+            Some(parse_quote!(a)),
+            parse_quote!(
+                trait RegAmbiguous {}
+            ),
+        );
+        register(
+            Some(parse_quote!(b)),
+            parse_quote!(
+                trait RegAmbiguous {}
+            ),
+        );
+
+        let Err(LookupError::Ambiguous(candidates)) = find_trait(&parse_quote!(RegAmbiguous))
+        else {
+            panic!("Expected an ambiguous lookup")
+        };
+
+        assert_eq!(candidates, vec!["a::RegAmbiguous", "b::RegAmbiguous"]);
+    }
+
+    #[test]
+    fn bare_registered_traits_with_same_name() {
+        register(
+            None,
+            parse_quote!(
+                trait RegCollision {
+                    fn a(&self);
+                }
+            ),
+        );
+
+        assert!(find_trait(&parse_quote!(a::RegCollision)).is_ok());
+
+        register(
+            None,
+            parse_quote!(
+                trait RegCollision {
+                    fn b(&self);
+                }
+            ),
+        );
+
+        assert!(matches!(
+            find_trait(&parse_quote!(a::RegCollision)),
+            Err(LookupError::Unqualified)
+        ));
+        assert!(matches!(
+            find_trait(&parse_quote!(RegCollision)),
+            Err(LookupError::Unqualified)
+        ));
+    }
+
+    #[test]
+    fn bare_registered_trait_matches_any_path() {
+        register(
+            None,
+            parse_quote!(
+                trait RegBare {}
+            ),
+        );
+
+        assert!(find_trait(&parse_quote!(RegBare)).is_ok());
+        assert!(find_trait(&parse_quote!(module::RegBare)).is_ok());
+    }
+
+    #[test]
+    fn stored_trait_is_a_last_resort() {
+        store::with_store_dir(store_dir("last-resort"), || {
+            save(
+                "net::RegStored",
+                parse_quote!(
+                    trait RegStored {
+                        fn old(&self);
+                    }
+                ),
+            );
+
+            // Enums wait for the trait to be tagged in this build.
+            assert!(matches!(
+                find_live_trait(&parse_quote!(net::RegStored)),
+                Err(LookupError::NotFound)
+            ));
+
+            let expect: ItemTrait = parse_quote!(
+                trait RegStored {
+                    fn old(&self);
+                }
+            );
+            assert_eq!(find_trait(&parse_quote!(net::RegStored)).unwrap(), expect);
+        });
+    }
+
+    #[test]
+    fn tagged_trait_shadows_stored_trait() {
+        store::with_store_dir(store_dir("shadowed"), || {
+            save(
+                "net::RegShadowed",
+                parse_quote!(
+                    trait RegShadowed {
+                        fn old(&self);
+                    }
+                ),
+            );
+
+            let item_trait: ItemTrait = parse_quote!(
+                trait RegShadowed {
+                    fn new(&self) -> u8;
+                }
+            );
+
+            assert!(register(Some(parse_quote!(net)), item_trait.clone()));
+            assert_eq!(find_trait(&parse_quote!(RegShadowed)).unwrap(), item_trait);
+            assert_eq!(
+                store::load_where(|key| key == "net::RegShadowed"),
+                vec![(
+                    "net::RegShadowed".to_string(),
+                    quote::quote!(#item_trait).to_string()
+                )]
+            );
+        });
+    }
+
+    #[test]
+    fn changed_stored_trait_is_reported() {
+        store::with_store_dir(store_dir("changed"), || {
+            save(
+                "RegChanged",
+                parse_quote!(
+                    trait RegChanged {
+                        fn later(&self) -> bool;
+                    }
+                ),
+            );
+            save(
+                "RegUnchanged",
+                parse_quote!(
+                    trait RegUnchanged {
+                        fn later(&self) -> bool;
+                    }
+                ),
+            );
+
+            assert!(find_trait(&parse_quote!(RegChanged)).is_ok());
+            assert!(find_trait(&parse_quote!(RegUnchanged)).is_ok());
+
+            assert!(!register(
+                None,
+                parse_quote!(
+                    trait RegChanged {
+                        fn later(&self) -> u8;
+                    }
+                )
+            ));
+            assert!(register(
+                None,
+                parse_quote!(
+                    trait RegUnchanged {
+                        fn later(&self) -> bool;
+                    }
+                )
+            ));
+        });
+    }
+
+    #[test]
+    fn outdated_stored_trait_is_removed() {
+        let dir = store_dir("outdated");
+
+        store::with_store_dir(dir.clone(), || {
+            save(
+                "RegOutdated",
+                parse_quote!(
+                    trait RegOutdated {}
+                ),
+            );
+
+            let file = dir.join("RegOutdated.trait");
+            let contents = fs::read_to_string(&file).unwrap();
+            fs::write(
+                &file,
+                contents.replacen("penum-registry/1", "penum-registry/0", 1),
+            )
+            .unwrap();
+
+            assert!(store::load_where(|_| true).is_empty());
+            assert!(!file.exists());
+        });
+    }
+}
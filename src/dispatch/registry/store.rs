@@ -0,0 +1,155 @@
+#[cfg(not(test))]
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+/// Bumped whenever the on-disk format changes. Entries written by a
+/// different format, or by a different version of penum, are removed
+/// when they are read.
+const STORE_VERSION: u32 = 1;
+
+/// Registered traits are also written to disk, so that a build that
+/// doesn't expand a supertrait before the trait that depends on it can
+/// still find it. Each trait is stored in its own file
+/// under `$OUT_DIR/penum`, or `target/penum/v1/<crate>`.
+///
+/// ```text
+/// penum-registry/1 0.1.29     <- Header
+/// net::Handler                <- Key
+/// trait Handler { .. }        <- Schematic
+/// ```
+#[cfg(not(test))]
+fn get_store_dir() -> Option<PathBuf> {
+    if let Some(out_dir) = env::var_os("OUT_DIR") {
+        return Some(PathBuf::from(out_dir).join("penum"));
+    }
+
+    let krate = env::var("CARGO_CRATE_NAME").ok()?;
+    let target = match env::var_os("CARGO_TARGET_DIR") {
+        Some(target) => PathBuf::from(target),
+        None => {
+            let manifest = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR")?);
+            manifest
+                .ancestors()
+                .map(|dir| dir.join("target"))
+                .find(|dir| dir.is_dir())
+                .unwrap_or_else(|| manifest.join("target"))
+        }
+    };
+
+    Some(
+        target
+            .join("penum")
+            .join(format!("v{STORE_VERSION}"))
+            .join(krate),
+    )
+}
+
+/// Unit tests shouldn't depend on what previous runs have written, so
+/// they have no store unless they set one up with `with_store_dir`.
+#[cfg(test)]
+fn get_store_dir() -> Option<PathBuf> {
+    STORE_DIR.with(|dir| dir.borrow().clone())
+}
+
+#[cfg(test)]
+thread_local! {
+    static STORE_DIR: std::cell::RefCell<Option<PathBuf>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Runs `f` with a store in an empty `dir`, which is removed afterwards.
+#[cfg(test)]
+pub fn with_store_dir<R>(dir: PathBuf, f: impl FnOnce() -> R) -> R {
+    let _ = fs::remove_dir_all(&dir);
+    STORE_DIR.with(|store| *store.borrow_mut() = Some(dir.clone()));
+
+    let result = f();
+
+    STORE_DIR.with(|store| *store.borrow_mut() = None);
+    let _ = fs::remove_dir_all(&dir);
+    result
+}
+
+fn get_header() -> String {
+    format!(
+        "penum-registry/{} {}",
+        STORE_VERSION,
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+fn get_file_name(key: &str) -> String {
+    format!("{}.trait", key.replace("::", "."))
+}
+
+/// Writes the schematic to disk, unless it's already there. Writes go
+/// through a temporary file so that a concurrent read never sees half
+/// an entry.
+pub fn save(key: &str, schematic: &str) {
+    let Some(dir) = get_store_dir() else {
+        return;
+    };
+
+    let file = dir.join(get_file_name(key));
+    let contents = format!("{}\n{}\n{}", get_header(), key, schematic);
+
+    if fs::read_to_string(&file).is_ok_and(|existing| existing == contents) {
+        return;
+    }
+
+    let tmp = dir.join(format!("{}.{}.tmp", get_file_name(key), process::id()));
+
+    // The registry is only a cache, so failing to write it is not an error.
+    let _ = fs::create_dir_all(&dir)
+        .and_then(|_| fs::write(&tmp, contents))
+        .and_then(|_| fs::rename(&tmp, &file));
+}
+
+/// Returns every stored entry whose key satisfies `f`. Entries that
+/// were written by another version are removed instead.
+pub fn load_where(f: impl Fn(&String) -> bool) -> Vec<(String, String)> {
+    let Some(entries) = get_store_dir().and_then(|dir| fs::read_dir(dir).ok()) else {
+        return vec![];
+    };
+
+    let header = get_header();
+    let mut found = vec![];
+
+    for path in entries.filter_map(|entry| Some(entry.ok()?.path())) {
+        if path.extension().is_none_or(|ext| ext != "trait") {
+            continue;
+        }
+
+        let Ok(contents) = fs::read_to_string(&path) else {
+            continue;
+        };
+
+        let mut lines = contents.splitn(3, '\n');
+
+        match (lines.next(), lines.next(), lines.next()) {
+            (Some(h), Some(key), Some(schematic)) if h == header => {
+                let key = key.to_string();
+                if f(&key) {
+                    found.push((key, schematic.to_string()));
+                }
+            }
+            _ => {
+                let _ = fs::remove_file(&path);
+            }
+        }
+    }
+
+    found
+}
+
+/// Removes every stored entry whose key satisfies `f`.
+pub fn remove_where(f: impl Fn(&String) -> bool) {
+    let Some(dir) = get_store_dir() else {
+        return;
+    };
+
+    for (key, _) in load_where(f) {
+        let _ = fs::remove_file(dir.join(get_file_name(&key)));
+    }
+}
@@ -1,11 +1,29 @@
 use proc_macro2::TokenStream;
 use quote::ToTokens;
+use syn::parse_quote;
 use syn::parse_str;
 use syn::spanned::Spanned;
+use syn::visit::visit_type_path;
+use syn::visit::Visit;
+use syn::visit_mut::VisitMut;
 use syn::ExprMacro;
 use syn::GenericArgument;
+use syn::ItemConst;
+use syn::Lifetime;
+use syn::Path;
 use syn::PathArguments;
+use syn::PathSegment;
 use syn::Type;
+use syn::TypeArray;
+use syn::TypeImplTrait;
+use syn::TypePath;
+
+use crate::utils::Stringify;
+use crate::utils::TypeUtils;
+
+use super::shm::SharedMemory;
+#[cfg(not(test))]
+use super::F_SHM;
 
 /// This is kind of a redundant solution..
 fn static_return<T: ToTokens + Spanned>(ty: &T) -> TokenStream {
@@ -14,7 +32,9 @@ fn static_return<T: ToTokens + Spanned>(ty: &T) -> TokenStream {
             use std::cell::UnsafeCell;
             use std::sync::Once;
             struct Static<T: Default>(UnsafeCell<Option<T>>, Once);
-            unsafe impl<T: Default> Sync for Static<T> {}
+            // SAFETY: `get` hands out `&'static T` to every thread that
+            // reaches the return position, so T itself has to be Sync.
+            unsafe impl<T: Default + Sync> Sync for Static<T> {}
             impl<T: Default> Static<T> {
                 pub const fn new() -> Self {
                     Self(UnsafeCell::new(None), Once::new())
@@ -48,6 +68,226 @@ fn static_return<T: ToTokens + Spanned>(ty: &T) -> TokenStream {
     )
 }
 
+/// Registers `item` as the fallback of its type, see `#[penum::fallback]`.
+/// The type is keyed by the module the const is in, if known, so that
+/// types with the same name in different modules are kept apart, e.g.
+/// `model::Status -> crate::model::STATUS_DEFAULT`.
+///
+/// Returns the const that was registered for the type before, if it has
+/// been overwritten by a different one.
+pub fn register_fallback(module: Option<&Path>, item: &ItemConst) -> Option<String> {
+    let ident = &item.ident;
+    let mut key = module.map(Stringify::get_string).unwrap_or_default();
+
+    if !key.is_empty() {
+        key.push_str(" :: ");
+    }
+
+    key.push_str(&get_fallback_key(&item.ty));
+
+    let value = match module {
+        Some(module) => quote::quote!(crate::#module::#ident).to_string(),
+        None => ident.to_string(),
+    };
+
+    with_fallbacks(|fallbacks| {
+        let previous = fallbacks.find(&key).filter(|previous| previous != &value);
+        fallbacks.insert(key, value);
+        previous
+    })
+}
+
+/// Looks for a const that has been registered as the fallback of `ty`.
+/// The path of `ty` has to match the end of the registered path, or the
+/// other way around, e.g. `Status` matches `model::Status`. A type that
+/// matches more than one fallback has none.
+///
+/// References are returned through a static, as a const can only be
+/// promoted when its type has no drop glue, e.g. `Cow<'static, str>`.
+fn registered_return(ty: &Type, is_ref: bool) -> Option<TokenStream> {
+    let key = get_fallback_key(ty);
+    let matches = |other: &String| {
+        other == &key
+            || other.ends_with(&format!(":: {key}"))
+            || key.ends_with(&format!(":: {other}"))
+    };
+
+    let value = with_fallbacks(|fallbacks| match fallbacks.find(&key) {
+        Some(value) => Some(value),
+        None => match fallbacks.find_all(matches).as_slice() {
+            [(_, value)] => Some(value.clone()),
+            _ => None,
+        },
+    })?;
+
+    let path: Path = parse_str(&value).ok()?;
+
+    if !is_ref {
+        return Some(quote::quote!(#path));
+    }
+
+    // The lifetimes of the return type can't be used in a static, and
+    // the const is `'static` anyway.
+    let mut ty = ty.clone();
+    StaticLifetimes.visit_type_mut(&mut ty);
+
+    Some(quote::quote!({
+        static FALLBACK: #ty = #path;
+        &FALLBACK
+    }))
+}
+
+/// The type of a fallback without `crate::` or `self::`, and with every
+/// lifetime set to `'static`, e.g. `Cow<'a, str>` becomes `Cow<'static,
+/// str>`.
+fn get_fallback_key(ty: &Type) -> String {
+    let mut ty = ty.clone();
+    StaticLifetimes.visit_type_mut(&mut ty);
+
+    if let Type::Path(TypePath { qself: None, path }) = &mut ty {
+        path.leading_colon = None;
+
+        while path.segments.len() > 1
+            && path
+                .segments
+                .first()
+                .is_some_and(|segment| segment.ident == "crate" || segment.ident == "self")
+        {
+            path.segments = path.segments.iter().skip(1).cloned().collect();
+        }
+    }
+
+    ty.get_string()
+}
+
+/// Registered fallbacks are shared by every expansion, except in unit
+/// tests, where each test gets its own so that they can't leak into
+/// each other.
+#[cfg(not(test))]
+fn with_fallbacks<R>(f: impl FnOnce(&SharedMemory<String, String>) -> R) -> R {
+    f(&F_SHM)
+}
+
+#[cfg(test)]
+fn with_fallbacks<R>(f: impl FnOnce(&SharedMemory<String, String>) -> R) -> R {
+    thread_local! {
+        static FALLBACKS: SharedMemory<String, String> = const { SharedMemory::new() };
+    }
+
+    FALLBACKS.with(f)
+}
+
+struct StaticLifetimes;
+
+impl VisitMut for StaticLifetimes {
+    fn visit_lifetime_mut(&mut self, node: &mut Lifetime) {
+        *node = Lifetime::new("'static", node.span());
+    }
+}
+
+/// The last type argument of a path segment, e.g. `T` in `Vec<T>`.
+fn get_last_type_argument(segment: &PathSegment) -> Option<&Type> {
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+
+    args.args.iter().rev().find_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })
+}
+
+/// `Box<T>` only implements `Default` when `T` does, which we can only
+/// tell for the types we know about.
+fn is_defaultable_pointee(segment: &PathSegment) -> bool {
+    get_last_type_argument(segment).is_some_and(|pointee| {
+        matches!(pointee, Type::Slice(_))
+            || matches!(pointee, Type::Path(path) if path.path.is_ident("str"))
+            || return_default_ret_type(pointee).is_some()
+    })
+}
+
+/// Types that can be stored in a static, i.e. that don't contain any
+/// lifetimes besides `'static`, or anything that looks like a generic.
+fn is_static_type(ty: &Type) -> bool {
+    struct IsStatic(bool);
+
+    impl Visit<'_> for IsStatic {
+        fn visit_lifetime(&mut self, lifetime: &Lifetime) {
+            self.0 &= lifetime.ident == "static";
+        }
+
+        fn visit_type_path(&mut self, path: &TypePath) {
+            self.0 &= !(path.qself.is_none()
+                && path.path.segments.len() == 1
+                && path.path.segments[0].arguments.is_empty()
+                && Type::Path(path.clone()).is_generic());
+
+            visit_type_path(self, path);
+        }
+
+        fn visit_type_impl_trait(&mut self, _: &TypeImplTrait) {
+            self.0 = false;
+        }
+    }
+
+    let mut visitor = IsStatic(true);
+    visitor.visit_type(ty);
+    visitor.0
+}
+
+/// Types whose fallback can be evaluated in a const context, i.e. that
+/// aren't allocated or built with `from_fn`.
+fn is_const_fallback(ty: &Type) -> bool {
+    struct IsConst(bool);
+
+    impl Visit<'_> for IsConst {
+        fn visit_type_path(&mut self, path: &TypePath) {
+            if let Some(segment) = path.path.segments.last() {
+                // `None` is const regardless of what it holds.
+                if segment.ident == "Option" {
+                    return;
+                }
+
+                self.0 &= !matches!(
+                    segment.ident.to_string().as_str(),
+                    "String"
+                        | "Vec"
+                        | "VecDeque"
+                        | "LinkedList"
+                        | "BinaryHeap"
+                        | "HashMap"
+                        | "HashSet"
+                        | "BTreeMap"
+                        | "BTreeSet"
+                        | "Box"
+                        | "Rc"
+                        | "Arc"
+                );
+            }
+
+            visit_type_path(self, path);
+        }
+
+        fn visit_type_array(&mut self, _: &TypeArray) {
+            self.0 = false;
+        }
+    }
+
+    let mut visitor = IsConst(true);
+    visitor.visit_type(ty);
+    visitor.0
+}
+
+/// Checks if a path is `io::Result`, e.g. `std::io::Result<usize>`.
+fn is_io_path(path: &syn::Path) -> bool {
+    path.segments
+        .iter()
+        .rev()
+        .nth(1)
+        .is_some_and(|seg| seg.ident == "io")
+}
+
 // We could use Visitor pattern here, but it was easier to do it like
 // this. TODO: Refactor please
 pub fn return_default_ret_type(mut ty: &Type) -> Option<TokenStream> {
@@ -68,8 +308,12 @@ pub fn return_default_ret_type(mut ty: &Type) -> Option<TokenStream> {
             // - &Result<i32, Option<T>> could also be defaulted to
             //   &Err(None)
             Type::Reference(ty_ref) => {
+                // An empty slice is the only `&'static mut` we can create.
                 if ty_ref.mutability.is_some() {
-                    return None;
+                    return matches!(&*ty_ref.elem, Type::Slice(_)).then(|| {
+                        tokens.extend(quote::quote!(&mut []));
+                        tokens
+                    });
                 }
 
                 is_ref = true;
@@ -90,8 +334,24 @@ pub fn return_default_ret_type(mut ty: &Type) -> Option<TokenStream> {
             //   Default, then we could just `Err(Default::default())`.
             //
             Type::Path(path) => {
+                if let Some(tokens) = registered_return(ty, is_ref) {
+                    return Some(tokens);
+                }
+
                 if let Some(path_seg) = path.path.segments.last() {
                     match path_seg.ident.to_string().as_str() {
+                        // `io::Result<T>` only has one argument, and
+                        // `io::Error` doesn't implement Default.
+                        "Result" if is_io_path(&path.path) => {
+                            if is_ref {
+                                return None;
+                            }
+
+                            tokens.extend(quote::quote!(Err(::std::io::Error::from(
+                                ::std::io::ErrorKind::Unsupported
+                            ))));
+                            return Some(tokens);
+                        }
                         "Result" => {
                             if let PathArguments::AngleBracketed(ref abga) = path_seg.arguments {
                                 if let Some(GenericArgument::Type(err_ty)) = abga.args.last() {
@@ -130,7 +390,63 @@ pub fn return_default_ret_type(mut ty: &Type) -> Option<TokenStream> {
                             tokens.extend(quote::quote!(0));
                             return Some(tokens);
                         }
-                        // "Result" => {}
+                        "f32" | "f64" => {
+                            tokens.extend(quote::quote!(0.0));
+                            return Some(tokens);
+                        }
+                        "Duration" => {
+                            tokens.extend(quote::quote!(::core::time::Duration::ZERO));
+                            return Some(tokens);
+                        }
+                        "PhantomData" => {
+                            tokens.extend(quote::quote!(::core::marker::PhantomData));
+                            return Some(tokens);
+                        }
+                        // `Cow<B>` borrows the fallback of `&B`, e.g.
+                        // `Cow::Borrowed("")` for `Cow<str>`.
+                        "Cow" => {
+                            let borrowed = get_last_type_argument(path_seg)?;
+                            let borrowed = return_default_ret_type(&parse_quote!(&#borrowed))?;
+
+                            tokens.extend(quote::quote!(::std::borrow::Cow::Borrowed(#borrowed)));
+                            return Some(tokens);
+                        }
+                        // Empty collections can't be promoted to a
+                        // `&'static`, so references are lazily created.
+                        "Vec" | "VecDeque" | "LinkedList" | "BinaryHeap" | "HashMap"
+                        | "HashSet" | "BTreeMap" | "BTreeSet" => {
+                            if is_ref {
+                                return is_static_type(ty).then(|| static_return(ty));
+                            }
+
+                            return Some(quote::quote!(::core::default::Default::default()));
+                        }
+                        // Smart pointers wrap the fallback of their pointee,
+                        // and unsized pointees are empty.
+                        "Box" | "Rc" | "Arc" => {
+                            if is_ref {
+                                return (path_seg.ident == "Box" && is_static_type(ty))
+                                    .then(|| static_return(ty))
+                                    .filter(|_| is_defaultable_pointee(path_seg));
+                            }
+
+                            let pointee = get_last_type_argument(path_seg)?;
+
+                            if matches!(pointee, Type::Slice(_))
+                                || matches!(pointee, Type::Path(path) if path.path.is_ident("str"))
+                            {
+                                return Some(quote::quote!(::core::default::Default::default()));
+                            }
+
+                            let value = return_default_ret_type(pointee)?;
+                            let ident = &path_seg.ident;
+
+                            return Some(match ident.to_string().as_str() {
+                                "Box" => quote::quote!(::std::boxed::Box::new(#value)),
+                                "Rc" => quote::quote!(::std::rc::Rc::new(#value)),
+                                _ => quote::quote!(::std::sync::Arc::new(#value)),
+                            });
+                        }
                         _ => return None,
                     }
                 };
@@ -138,11 +454,37 @@ pub fn return_default_ret_type(mut ty: &Type) -> Option<TokenStream> {
                 return None;
             }
 
+            // Only reachable through a reference, e.g. `&[T]`.
+            Type::Slice(_) => {
+                tokens.extend(quote::quote!([]));
+                return Some(tokens);
+            }
+
+            // References have to be promoted, which `from_fn` can't be,
+            // so the element has to be const instead.
+            Type::Array(array) => {
+                let value = return_default_ret_type(&array.elem)?;
+                let len = &array.len;
+
+                if is_ref {
+                    if !is_const_fallback(&array.elem) {
+                        return None;
+                    }
+
+                    tokens.extend(quote::quote!([const { #value }; #len]));
+                } else {
+                    tokens.extend(quote::quote!(::core::array::from_fn(|_| #value)));
+                }
+
+                return Some(tokens);
+            }
+
             Type::Tuple(tuple) => {
                 let len = tuple.elems.len();
 
                 if len == 0 {
-                    return None;
+                    tokens.extend(quote::quote!(()));
+                    return Some(tokens);
                 }
 
                 let mut group = TokenStream::new();
@@ -178,8 +520,8 @@ pub fn return_panic() -> TokenStream {
 
 #[cfg(test)]
 mod tests {
-    use crate::dispatch::ret::return_default_ret_type;
-    use syn::{parse_quote, Type};
+    use crate::dispatch::ret::{register_fallback, registered_return, return_default_ret_type};
+    use syn::{parse_quote, ItemConst, Path, Type};
 
     #[test]
     fn owned_result() {
@@ -197,6 +539,17 @@ mod tests {
         assert_eq!("& Err (\"\" . to_string ())", result.as_str())
     }
 
+    #[test]
+    fn owned_io_result() {
+        let ty: Type = parse_quote!(::std::io::Result<usize>);
+        let result = return_default_ret_type(&ty).expect("to parse").to_string();
+
+        assert_eq!(
+            "Err (:: std :: io :: Error :: from (:: std :: io :: ErrorKind :: Unsupported))",
+            result.as_str()
+        )
+    }
+
     #[test]
     fn owned_option() {
         let ty: Type = parse_quote!(Option<T>);
@@ -258,7 +611,7 @@ mod tests {
         let ty: Type = parse_quote!(&String);
         let result = return_default_ret_type(&ty).expect("to parse").to_string();
 
-        assert_eq!("{ use std :: cell :: UnsafeCell ; use std :: sync :: Once ; struct Static < T : Default > (UnsafeCell < Option < T >> , Once) ; unsafe impl < T : Default > Sync for Static < T > { } impl < T : Default > Static < T > { pub const fn new () -> Self { Self (UnsafeCell :: new (None) , Once :: new ()) } fn get (& self) -> & 'static T { self . 1 . call_once (|| unsafe { * self . 0 . get () = Some (T :: default ()) }) ; unsafe { (* self . 0 . get ()) . as_ref () . unwrap_unchecked () } } } static RETURN : Static < String > = Static :: new () ; RETURN . get () }", result.as_str())
+        assert_eq!("{ use std :: cell :: UnsafeCell ; use std :: sync :: Once ; struct Static < T : Default > (UnsafeCell < Option < T >> , Once) ; unsafe impl < T : Default + Sync > Sync for Static < T > { } impl < T : Default > Static < T > { pub const fn new () -> Self { Self (UnsafeCell :: new (None) , Once :: new ()) } fn get (& self) -> & 'static T { self . 1 . call_once (|| unsafe { * self . 0 . get () = Some (T :: default ()) }) ; unsafe { (* self . 0 . get ()) . as_ref () . unwrap_unchecked () } } } static RETURN : Static < String > = Static :: new () ; RETURN . get () }", result.as_str())
     }
 
     #[test]
@@ -297,4 +650,295 @@ mod tests {
             result.as_str()
         )
     }
+
+    #[test]
+    fn owned_registered() {
+        let item: ItemConst = parse_quote!(
+            const STATUS_DEFAULT: Status = Status::Unknown;
+        );
+        register_fallback(None, &item);
+
+        let ty: Type = parse_quote!(Status);
+        let result = return_default_ret_type(&ty).expect("to parse").to_string();
+
+        assert_eq!("STATUS_DEFAULT", result.as_str())
+    }
+
+    #[test]
+    fn ref_registered() {
+        let item: ItemConst = parse_quote!(
+            const NAME_DEFAULT: Cow<'static, str> = Cow::Borrowed("");
+        );
+        register_fallback(None, &item);
+
+        let ty: Type = parse_quote!(&Cow<'static, str>);
+        let result = return_default_ret_type(&ty).expect("to parse").to_string();
+
+        assert_eq!(
+            "{ static FALLBACK : Cow < 'static , str > = NAME_DEFAULT ; & FALLBACK }",
+            result.as_str()
+        )
+    }
+
+    #[test]
+    fn tuple_registered() {
+        let item: ItemConst = parse_quote!(
+            const SPAN_DEFAULT: Span = Span::EMPTY;
+        );
+        register_fallback(None, &item);
+
+        let ty: Type = parse_quote!((Span, Option<Span>));
+        let result = return_default_ret_type(&ty).expect("to parse").to_string();
+
+        assert_eq!("(SPAN_DEFAULT , None)", result.as_str())
+    }
+
+    #[test]
+    fn path_registered() {
+        let module: Path = parse_quote!(model);
+        let item: ItemConst = parse_quote!(
+            const STATUS_DEFAULT: Status = Status::Unknown;
+        );
+        assert!(register_fallback(Some(&module), &item).is_none());
+
+        for ty in [
+            parse_quote!(Status),
+            parse_quote!(model::Status),
+            parse_quote!(crate::model::Status),
+        ] {
+            let result = return_default_ret_type(&ty).expect("to parse").to_string();
+            assert_eq!("crate :: model :: STATUS_DEFAULT", result.as_str());
+        }
+
+        let ty: Type = parse_quote!(other::Status);
+        assert!(registered_return(&ty, false).is_none());
+    }
+
+    #[test]
+    fn same_name_registered() {
+        let (model, view): (Path, Path) = (parse_quote!(model), parse_quote!(view));
+        let item: ItemConst = parse_quote!(
+            const STATUS_DEFAULT: Status = Status::Unknown;
+        );
+        register_fallback(Some(&model), &item);
+        register_fallback(Some(&view), &item);
+
+        let ty: Type = parse_quote!(view::Status);
+        let result = registered_return(&ty, false).expect("to parse").to_string();
+        assert_eq!("crate :: view :: STATUS_DEFAULT", result.as_str());
+
+        // A bare name could be either of them.
+        let ty: Type = parse_quote!(Status);
+        assert!(registered_return(&ty, false).is_none());
+    }
+
+    #[test]
+    fn overwritten_registered() {
+        let item: ItemConst = parse_quote!(
+            const STATUS_DEFAULT: Status = Status::Unknown;
+        );
+        let other: ItemConst = parse_quote!(
+            const STATUS_OTHER: Status = Status::Up;
+        );
+
+        assert!(register_fallback(None, &item).is_none());
+        assert!(register_fallback(None, &item).is_none());
+        assert_eq!(
+            register_fallback(None, &other).as_deref(),
+            Some("STATUS_DEFAULT")
+        );
+    }
+
+    #[test]
+    fn ref_lifetime_registered() {
+        let item: ItemConst = parse_quote!(
+            const NAME_DEFAULT: Cow<'static, str> = Cow::Borrowed("");
+        );
+        register_fallback(None, &item);
+
+        let ty: Type = parse_quote!(&'a Cow<'a, str>);
+        let result = return_default_ret_type(&ty).expect("to parse").to_string();
+
+        assert_eq!(
+            "{ static FALLBACK : Cow < 'static , str > = NAME_DEFAULT ; & FALLBACK }",
+            result.as_str()
+        )
+    }
+
+    #[test]
+    fn owned_float() {
+        let ty: Type = parse_quote!(f64);
+        let result = return_default_ret_type(&ty).expect("to parse").to_string();
+
+        assert_eq!("0.0", result.as_str())
+    }
+
+    #[test]
+    fn ref_float() {
+        let ty: Type = parse_quote!(&f32);
+        let result = return_default_ret_type(&ty).expect("to parse").to_string();
+
+        assert_eq!("& 0.0", result.as_str())
+    }
+
+    #[test]
+    fn unit() {
+        let ty: Type = parse_quote!(());
+        let result = return_default_ret_type(&ty).expect("to parse").to_string();
+
+        assert_eq!("()", result.as_str())
+    }
+
+    #[test]
+    fn ref_slice() {
+        let ty: Type = parse_quote!(&[String]);
+        let result = return_default_ret_type(&ty).expect("to parse").to_string();
+
+        assert_eq!("& []", result.as_str())
+    }
+
+    #[test]
+    fn mut_slice() {
+        let ty: Type = parse_quote!(&'a mut [u8]);
+        let result = return_default_ret_type(&ty).expect("to parse").to_string();
+
+        assert_eq!("& mut []", result.as_str())
+    }
+
+    #[test]
+    fn mut_number() {
+        let ty: Type = parse_quote!(&mut u8);
+
+        assert!(return_default_ret_type(&ty).is_none())
+    }
+
+    #[test]
+    fn owned_array() {
+        let ty: Type = parse_quote!([String; 3]);
+        let result = return_default_ret_type(&ty).expect("to parse").to_string();
+
+        assert_eq!(
+            ":: core :: array :: from_fn (| _ | \"\" . to_string ())",
+            result.as_str()
+        )
+    }
+
+    #[test]
+    fn ref_array() {
+        let ty: Type = parse_quote!(&[Option<String>; 4]);
+        let result = return_default_ret_type(&ty).expect("to parse").to_string();
+
+        assert_eq!("& [const { None } ; 4]", result.as_str())
+    }
+
+    #[test]
+    fn ref_array_non_const() {
+        let ty: Type = parse_quote!(&[String; 4]);
+
+        assert!(return_default_ret_type(&ty).is_none())
+    }
+
+    #[test]
+    fn owned_vec() {
+        let ty: Type = parse_quote!(Vec<T>);
+        let result = return_default_ret_type(&ty).expect("to parse").to_string();
+
+        assert_eq!(
+            ":: core :: default :: Default :: default ()",
+            result.as_str()
+        )
+    }
+
+    #[test]
+    fn ref_hash_map() {
+        let ty: Type = parse_quote!(&HashMap<String, usize>);
+        let result = return_default_ret_type(&ty).expect("to parse").to_string();
+
+        assert!(result.contains("static RETURN : Static < HashMap < String , usize > >"))
+    }
+
+    #[test]
+    fn ref_generic_vec() {
+        let ty: Type = parse_quote!(&Vec<T>);
+
+        assert!(return_default_ret_type(&ty).is_none())
+    }
+
+    #[test]
+    fn owned_cow_str() {
+        let ty: Type = parse_quote!(Cow<'a, str>);
+        let result = return_default_ret_type(&ty).expect("to parse").to_string();
+
+        assert_eq!(
+            ":: std :: borrow :: Cow :: Borrowed (\"\")",
+            result.as_str()
+        )
+    }
+
+    #[test]
+    fn ref_cow_slice() {
+        let ty: Type = parse_quote!(&Cow<[u8]>);
+        let result = return_default_ret_type(&ty).expect("to parse").to_string();
+
+        assert_eq!(
+            "& :: std :: borrow :: Cow :: Borrowed (& [])",
+            result.as_str()
+        )
+    }
+
+    #[test]
+    fn owned_box() {
+        let ty: Type = parse_quote!(Box<(bool, Option<T>)>);
+        let result = return_default_ret_type(&ty).expect("to parse").to_string();
+
+        assert_eq!(
+            ":: std :: boxed :: Box :: new ((false , None))",
+            result.as_str()
+        )
+    }
+
+    #[test]
+    fn owned_rc_str() {
+        let ty: Type = parse_quote!(Rc<str>);
+        let result = return_default_ret_type(&ty).expect("to parse").to_string();
+
+        assert_eq!(
+            ":: core :: default :: Default :: default ()",
+            result.as_str()
+        )
+    }
+
+    #[test]
+    fn owned_arc_vec() {
+        let ty: Type = parse_quote!(Arc<Vec<u8>>);
+        let result = return_default_ret_type(&ty).expect("to parse").to_string();
+
+        assert_eq!(
+            ":: std :: sync :: Arc :: new (:: core :: default :: Default :: default ())",
+            result.as_str()
+        )
+    }
+
+    #[test]
+    fn ref_rc() {
+        let ty: Type = parse_quote!(&Rc<u8>);
+
+        assert!(return_default_ret_type(&ty).is_none())
+    }
+
+    #[test]
+    fn duration() {
+        let ty: Type = parse_quote!(&std::time::Duration);
+        let result = return_default_ret_type(&ty).expect("to parse").to_string();
+
+        assert_eq!("& :: core :: time :: Duration :: ZERO", result.as_str())
+    }
+
+    #[test]
+    fn phantom_data() {
+        let ty: Type = parse_quote!(PhantomData<T>);
+        let result = return_default_ret_type(&ty).expect("to parse").to_string();
+
+        assert_eq!(":: core :: marker :: PhantomData", result.as_str())
+    }
 }
@@ -30,9 +30,45 @@ impl<K, V> SharedMemory<K, V> {
         Q: Ord,
     {
         if let Ok(s) = self.0.lock() {
-            s.get(key).map(Clone::clone)
+            s.get(key).cloned()
         } else {
             None
         }
     }
+
+    /// Returns every entry whose key satisfies `f`.
+    pub fn find_all(&self, f: impl Fn(&K) -> bool) -> Vec<(K, V)>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        if let Ok(s) = self.0.lock() {
+            s.iter()
+                .filter(|(key, _)| f(key))
+                .map(|(key, val)| (key.clone(), val.clone()))
+                .collect()
+        } else {
+            vec![]
+        }
+    }
+
+    /// Removes and returns every entry whose value satisfies `f`.
+    pub fn take_where(&self, f: impl Fn(&V) -> bool) -> Vec<(K, V)>
+    where
+        K: Ord + Clone,
+    {
+        if let Ok(ref mut s) = self.0.lock() {
+            let keys = s
+                .iter()
+                .filter(|(_, val)| f(val))
+                .map(|(key, _)| key.clone())
+                .collect::<Vec<_>>();
+
+            keys.into_iter()
+                .filter_map(|key| s.remove_entry(&key))
+                .collect()
+        } else {
+            vec![]
+        }
+    }
 }
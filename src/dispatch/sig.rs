@@ -1,6 +1,7 @@
 use std::ops::Deref;
 
 use proc_macro2::Span;
+use proc_macro2::TokenStream;
 
 use syn::parse_quote;
 use syn::punctuated::Punctuated;
@@ -8,21 +9,39 @@ use syn::spanned::Spanned;
 use syn::token;
 use syn::token::Comma;
 use syn::Arm;
+use syn::Expr;
 use syn::Field;
+use syn::Fields;
 use syn::FnArg;
 use syn::Ident;
 use syn::Pat;
+use syn::PatType;
+use syn::Path;
+use syn::Receiver;
 use syn::Signature;
 use syn::TraitItemMethod;
+use syn::Type;
+use syn::TypePath;
 
+use quote::format_ident;
 use quote::ToTokens;
 
+use crate::factory::DerefLayer;
+use crate::utils::is_dispatch_attr;
+
 #[derive(Debug)]
 pub struct VariantSig<'info> {
     enum_ident: &'info Ident,
     variant_ident: &'info Ident,
+    field: &'info Field,
+    field_index: usize,
+    fields: &'info Fields,
     caller: Ident,
     params: Composite,
+
+    /// Set when the user has explicitly marked this field for dispatch,
+    /// either through `^` in the pattern or `#[penum(dispatch)]`.
+    marked: bool,
 }
 
 /// For each <Dispatchable> -> <{ position, ident, fields }> Used to
@@ -73,40 +92,277 @@ impl<'info> VariantSig<'info> {
     pub fn new(
         enum_ident: &'info Ident,
         variant_ident: &'info Ident,
-        field: &Field,
+        field: &'info Field,
         field_index: usize,
-        max_length: usize,
+        fields: &'info Fields,
     ) -> Self {
         let position = Position::from_field(field, field_index);
         let caller = position.get_caller();
-        let fields = position.format_fields_pattern(max_length);
+        let params = position.format_fields_pattern(fields.len());
 
         Self {
             enum_ident,
             variant_ident,
+            field,
+            field_index,
+            fields,
             caller,
-            params: fields,
+            params,
+            marked: field.attrs.iter().any(is_dispatch_attr),
         }
     }
 
+    /// Used to carry over a `^` marker from the matched pattern field.
+    pub fn with_marker(mut self, marked: bool) -> Self {
+        self.marked |= marked;
+        self
+    }
+
+    pub fn get_enum_ident(&self) -> &Ident {
+        self.enum_ident
+    }
+
+    pub fn get_variant_ident(&self) -> &Ident {
+        self.variant_ident
+    }
+
+    pub fn get_field(&self) -> &Field {
+        self.field
+    }
+
+    pub fn get_field_index(&self) -> usize {
+        self.field_index
+    }
+
+    pub fn is_marked(&self) -> bool {
+        self.marked
+    }
+
     /// To be able to construct a dispatch arm we would need two things,
     /// a variant signature and a trait item containing a method ident
     /// and inputs.
-    pub fn parse_arm(&'info self, method: &'info TraitItemMethod) -> (&Ident, Arm) {
+    ///
+    /// Methods are called through `trait_path` when it's given, e.g.
+    /// `Debug::fmt(val, f)` instead of `val.fmt(f)`.
+    ///
+    /// `pairs` are arguments of type `Self` that have to be the same
+    /// variant, e.g. `(Foo::Bar(val), Foo::Bar(other)) => val.eq(other)`.
+    pub fn parse_arm(
+        &'info self,
+        method: &'info TraitItemMethod,
+        trait_path: Option<&Path>,
+        pairs: &[Ident],
+    ) -> (&'info Ident, Arm) {
         let Self {
             enum_ident,
             variant_ident,
-            caller,
             params: fields,
             ..
         } = self;
 
+        let (method_ident, call) = self.get_call(method, trait_path);
+        let pat: Pat = parse_quote!(#enum_ident :: #variant_ident #fields);
+        let pat = self.pair_pattern(pat, pairs);
+
+        (method_ident, parse_quote! {#pat => #call})
+    }
+
+    /// Same as `parse_arm`, but the dispatched field is wrapped, e.g. in
+    /// a `Box` or an `Option`. Each layer is peeled off before the call,
+    /// and `fallback` is returned when an `Option` is `None`. Pinned
+    /// methods are never dispatched through wrappers, see
+    /// `Blueprint::get_pinned_deref`.
+    ///
+    /// ```text
+    /// Expr::Neg(val) => { let val = &**val; val.eval() }
+    /// Expr::Opt(val) => match val { Some(val) => val.eval(), None => 0 }
+    /// ```
+    pub fn parse_deref_arm(
+        &'info self,
+        method: &'info TraitItemMethod,
+        trait_path: Option<&Path>,
+        layers: &[DerefLayer],
+        fallback: &TokenStream,
+    ) -> (&'info Ident, Arm) {
+        let Self {
+            enum_ident,
+            variant_ident,
+            params: fields,
+            caller,
+            ..
+        } = self;
+
+        let (method_ident, mut call) = self.get_call(method, trait_path);
+
+        let deref: Expr = match method.sig.receiver() {
+            Some(FnArg::Receiver(Receiver {
+                reference: Some(_),
+                mutability: Some(_),
+                ..
+            })) => parse_quote!(&mut **#caller),
+            Some(FnArg::Receiver(Receiver {
+                reference: Some(_), ..
+            })) => parse_quote!(&**#caller),
+            _ => parse_quote!(*#caller),
+        };
+
+        for layer in layers.iter().rev() {
+            call = match layer {
+                DerefLayer::Deref => parse_quote!({
+                    let #caller = #deref;
+                    #call
+                }),
+                DerefLayer::Option => parse_quote!(match #caller {
+                    ::core::option::Option::Some(#caller) => #call,
+                    ::core::option::Option::None => #fallback,
+                }),
+            };
+        }
+
+        let pat: Pat = parse_quote!(#enum_ident :: #variant_ident #fields);
+
+        (method_ident, parse_quote! {#pat => #call})
+    }
+
+    /// Same as `parse_arm`, but for methods that return `Self`. Every
+    /// field is bound so that the variant can be rebuilt around the
+    /// result, e.g. `Foo::Bar(field0, val) => Foo::Bar(field0, val.neg())`.
+    ///
+    /// The other fields are moved into the new variant when the method
+    /// takes `self`, and cloned when it only borrows it.
+    pub fn parse_wrapped_arm(
+        &'info self,
+        method: &'info TraitItemMethod,
+        trait_path: Option<&Path>,
+        pairs: &[Ident],
+    ) -> (&'info Ident, Arm) {
+        let Self {
+            enum_ident,
+            variant_ident,
+            fields,
+            field_index,
+            caller,
+            ..
+        } = self;
+
+        let (method_ident, call) = self.get_call(method, trait_path);
+
+        let borrows_self = matches!(
+            method.sig.receiver(),
+            Some(FnArg::Receiver(Receiver {
+                reference: Some(_),
+                ..
+            }))
+        );
+
+        let binders = fields
+            .iter()
+            .enumerate()
+            .map(
+                |(index, field)| match (&field.ident, index == *field_index) {
+                    (Some(ident), _) => ident.clone(),
+                    (None, true) => caller.clone(),
+                    (None, false) => format_ident!("field{}", index, span = field.span()),
+                },
+            )
+            .collect::<Vec<_>>();
+
+        let values = binders.iter().enumerate().map(|(index, binder)| -> Expr {
+            if index == *field_index {
+                call.clone()
+            } else if borrows_self {
+                parse_quote!(::core::clone::Clone::clone(#binder))
+            } else {
+                parse_quote!(#binder)
+            }
+        });
+
+        let (pat, variant): (Pat, Expr) = match fields {
+            Fields::Named(_) => (
+                parse_quote!(#enum_ident :: #variant_ident { #(#binders),* }),
+                parse_quote!(#enum_ident :: #variant_ident { #(#binders: #values),* }),
+            ),
+            Fields::Unnamed(_) => (
+                parse_quote!(#enum_ident :: #variant_ident ( #(#binders),* )),
+                parse_quote!(#enum_ident :: #variant_ident ( #(#values),* )),
+            ),
+            Fields::Unit => return self.parse_arm(method, trait_path, pairs),
+        };
+
+        let pat = self.pair_pattern(pat, pairs);
+
+        (method_ident, parse_quote! {#pat => #variant})
+    }
+
+    /// Turns the pattern of `self` into a tuple pattern that also
+    /// matches `pairs`. The dispatched field of a pair is bound to the
+    /// name of the argument, which shadows the argument itself.
+    fn pair_pattern(&self, pat: Pat, pairs: &[Ident]) -> Pat {
+        if pairs.is_empty() {
+            return pat;
+        }
+
+        let Self {
+            enum_ident,
+            variant_ident,
+            field,
+            field_index,
+            ..
+        } = self;
+
+        let pairs = pairs.iter().map(|pair| -> Pat {
+            match &field.ident {
+                Some(key) => parse_quote!(#enum_ident :: #variant_ident { #key: #pair, .. }),
+                None => {
+                    let skipped = (0..*field_index).map(|_| token::Underscore(Span::call_site()));
+                    parse_quote!(#enum_ident :: #variant_ident ( #(#skipped,)* #pair, .. ))
+                }
+            }
+        });
+
+        parse_quote!((#pat, #(#pairs),*))
+    }
+
+    fn get_call(
+        &'info self,
+        method: &'info TraitItemMethod,
+        trait_path: Option<&Path>,
+    ) -> (&'info Ident, Expr) {
         let (method_ident, sanitized_input) = get_method_parts(method);
 
-        (
-            method_ident,
-            parse_quote! {#enum_ident :: #variant_ident #fields => #caller . #method_ident (#sanitized_input)},
-        )
+        // SAFETY: The enum is pinned, and so are its dispatched fields, see
+        // `dispatch::pin`. The field is never moved out of the enum.
+        let caller: Expr = if is_pinned(&method.sig) {
+            let caller = &self.caller;
+            parse_quote!((unsafe { ::core::pin::Pin::new_unchecked(#caller) }))
+        } else {
+            let caller = &self.caller;
+            parse_quote!(#caller)
+        };
+
+        let call: Expr = match trait_path {
+            // Callables are called directly, see `Blueprint::is_callable`.
+            // The field is moved into a temporary when we own it, so that
+            // an `FnMut` can be called without a `mut` binding.
+            _ if is_rust_call(&method.sig) && is_owned_receiver(&method.sig) => {
+                parse_quote!(({ #caller })(#sanitized_input))
+            }
+            _ if is_rust_call(&method.sig) => parse_quote!(#caller(#sanitized_input)),
+            Some(path) if sanitized_input.is_empty() => parse_quote!(#path::#method_ident(#caller)),
+            Some(path) => parse_quote!(#path::#method_ident(#caller, #sanitized_input)),
+            None => parse_quote!(#caller . #method_ident (#sanitized_input)),
+        };
+
+        // SAFETY: We are inside the `unsafe fn` of the enum, which has the
+        // same safety contract as the method we delegate to. The caller has
+        // therefore already upheld it, and the arguments are passed on
+        // untouched.
+        let call = match method.sig.unsafety {
+            Some(unsafety) => parse_quote!(#unsafety { #call }),
+            None => call,
+        };
+
+        (method_ident, call)
     }
 }
 
@@ -174,12 +430,88 @@ impl ToTokens for Param {
     }
 }
 
+/// Rebinds every typed parameter to a hygienic identifier, so that any
+/// parameter pattern can be forwarded as an argument. Attributes on the
+/// parameters are kept, and the original patterns are returned.
+///
+/// ```text
+/// fn f(&self, _: u32, (a, b): (u8, u8), mut x: T);
+/// fn f(&self, arg0: u32, arg1: (u8, u8), arg2: T);
+/// ```
+pub fn rebind_params(signature: &mut Signature) -> Vec<Pat> {
+    signature
+        .inputs
+        .iter_mut()
+        .filter_map(|arg| match arg {
+            FnArg::Typed(typed) if !is_typed_receiver(typed) => Some(typed),
+            _ => None,
+        })
+        .enumerate()
+        .map(|(index, typed)| {
+            let ident = Ident::new(&format!("arg{index}"), Span::mixed_site());
+            std::mem::replace(&mut *typed.pat, parse_quote!(#ident))
+        })
+        .collect()
+}
+
+/// The hygienic identifiers of `rebind_params`.
+pub fn get_rebound_params(signature: &Signature) -> Vec<&Pat> {
+    signature
+        .inputs
+        .iter()
+        .filter_map(|arg| match arg {
+            FnArg::Typed(typed) if !is_typed_receiver(typed) => Some(&*typed.pat),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Methods taking `self: Pin<&mut Self>`, e.g. `Future::poll`. These
+/// are dispatched through a pin projection of the dispatched field.
+pub fn is_pinned(signature: &Signature) -> bool {
+    match signature.inputs.first() {
+        Some(FnArg::Typed(typed)) if is_typed_receiver(typed) => matches!(
+            typed.ty.as_ref(),
+            Type::Path(TypePath { path, .. })
+                if path.segments.last().is_some_and(|segment| segment.ident == "Pin")
+        ),
+        _ => false,
+    }
+}
+
+/// The `Fn*` traits, whose arguments are passed as a tuple.
+pub fn is_rust_call(signature: &Signature) -> bool {
+    signature
+        .abi
+        .as_ref()
+        .and_then(|abi| abi.name.as_ref())
+        .is_some_and(|name| name.value() == "rust-call")
+}
+
+/// `self` or `mut self`.
+fn is_owned_receiver(signature: &Signature) -> bool {
+    matches!(
+        signature.receiver(),
+        Some(FnArg::Receiver(Receiver {
+            reference: None,
+            ..
+        }))
+    )
+}
+
+/// syn only parses `self`, `&self` and `mut self` as receivers, so a
+/// `self: Pin<&mut Self>` ends up as a typed argument.
+fn is_typed_receiver(typed: &PatType) -> bool {
+    matches!(typed.pat.as_ref(), Pat::Ident(pat) if pat.ident == "self")
+}
+
 fn sanitize(inputs: &Punctuated<FnArg, Comma>) -> Punctuated<Pat, Comma> {
     let mut san = Punctuated::new();
     let max = inputs.len();
 
     inputs.iter().enumerate().for_each(|(i, arg)| match arg {
         syn::FnArg::Receiver(_) => (),
+        syn::FnArg::Typed(typed) if is_typed_receiver(typed) => (),
         syn::FnArg::Typed(typed) => {
             san.push_value(typed.pat.deref().clone());
             if i != max - 1 {
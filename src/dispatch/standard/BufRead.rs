@@ -0,0 +1,9 @@
+pub trait BufRead: Read {
+    fn fill_buf(&mut self) -> ::std::io::Result<&[u8]>;
+
+    fn consume(&mut self, amt: usize);
+
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> ::std::io::Result<usize> { .. }
+
+    fn read_line(&mut self, buf: &mut String) -> ::std::io::Result<usize> { .. }
+}
\ No newline at end of file
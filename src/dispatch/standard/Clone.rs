@@ -0,0 +1,3 @@
+pub trait Clone {
+    fn clone(&self) -> Self;
+}
\ No newline at end of file
@@ -1,3 +1,3 @@
 pub trait Debug {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error>;
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result;
 }
@@ -0,0 +1,3 @@
+pub trait Error: Debug + Display {
+    fn source(&self) -> Option<&(dyn ::std::error::Error + 'static)>;
+}
\ No newline at end of file
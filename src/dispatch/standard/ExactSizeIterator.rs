@@ -0,0 +1,3 @@
+pub trait ExactSizeIterator: Iterator {
+    fn len(&self) -> usize;
+}
\ No newline at end of file
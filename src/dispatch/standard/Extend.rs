@@ -0,0 +1,3 @@
+pub trait Extend<A> {
+    fn extend<T: IntoIterator<Item = A>>(&mut self, iter: T);
+}
\ No newline at end of file
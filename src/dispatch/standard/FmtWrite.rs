@@ -0,0 +1,7 @@
+pub trait Write {
+    fn write_str(&mut self, s: &str) -> ::core::fmt::Result;
+
+    fn write_char(&mut self, c: char) -> ::core::fmt::Result { .. }
+
+    fn write_fmt(&mut self, args: ::core::fmt::Arguments<'_>) -> ::core::fmt::Result { .. }
+}
\ No newline at end of file
@@ -1,3 +1,3 @@
 pub trait Hash {
-    fn hash<H: ~const Hasher>(&self, state: &mut H);
+    fn hash<H: ::core::hash::Hasher>(&self, state: &mut H);
 }
\ No newline at end of file
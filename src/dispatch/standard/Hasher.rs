@@ -0,0 +1,13 @@
+pub trait Hasher {
+    fn finish(&self) -> u64;
+
+    fn write(&mut self, bytes: &[u8]);
+
+    fn write_u8(&mut self, i: u8) { .. }
+
+    fn write_u32(&mut self, i: u32) { .. }
+
+    fn write_u64(&mut self, i: u64) { .. }
+
+    fn write_usize(&mut self, i: usize) { .. }
+}
\ No newline at end of file
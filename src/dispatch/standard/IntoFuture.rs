@@ -1,6 +1,6 @@
 pub trait IntoFuture {
     type Output;
-    type IntoFuture: Future<Output = Self::Output>;
+    type IntoFuture: ::core::future::Future<Output = Self::Output>;
 
     fn into_future(self) -> Self::IntoFuture;
 }
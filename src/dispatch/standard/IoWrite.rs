@@ -0,0 +1,11 @@
+pub trait Write {
+    fn write(&mut self, buf: &[u8]) -> ::std::io::Result<usize>;
+
+    fn flush(&mut self) -> ::std::io::Result<()>;
+
+    fn write_all(&mut self, buf: &[u8]) -> ::std::io::Result<()> { .. }
+
+    fn write_vectored(&mut self, bufs: &[::std::io::IoSlice<'_>]) -> ::std::io::Result<usize> { .. }
+
+    fn write_fmt(&mut self, fmt: ::std::fmt::Arguments<'_>) -> ::std::io::Result<()> { .. }
+}
\ No newline at end of file
@@ -2,4 +2,14 @@ pub trait Iterator {
     type Item;
 
     fn next(&mut self) -> Option<Self::Item>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) { .. }
+
+    fn count(self) -> usize where Self: Sized { .. }
+
+    fn last(self) -> Option<Self::Item> where Self: Sized { .. }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> { .. }
+
+    fn fold<B, F>(self, init: B, f: F) -> B where Self: Sized, F: FnMut(B, Self::Item) -> B { .. }
 }
\ No newline at end of file
@@ -0,0 +1,3 @@
+pub trait Ord: Eq + PartialOrd<Self> {
+    fn cmp(&self, other: &Self) -> ::core::cmp::Ordering;
+}
\ No newline at end of file
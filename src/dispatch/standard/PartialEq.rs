@@ -0,0 +1,3 @@
+pub trait PartialEq<Rhs: ?Sized = Self> {
+    fn eq(&self, other: &Rhs) -> bool;
+}
\ No newline at end of file
@@ -0,0 +1,3 @@
+pub trait PartialOrd<Rhs: ?Sized = Self>: PartialEq<Rhs> {
+    fn partial_cmp(&self, other: &Rhs) -> Option<::core::cmp::Ordering>;
+}
\ No newline at end of file
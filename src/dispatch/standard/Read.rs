@@ -0,0 +1,11 @@
+pub trait Read {
+    fn read(&mut self, buf: &mut [u8]) -> ::std::io::Result<usize>;
+
+    fn read_vectored(&mut self, bufs: &mut [::std::io::IoSliceMut<'_>]) -> ::std::io::Result<usize> { .. }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> ::std::io::Result<()> { .. }
+
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> ::std::io::Result<usize> { .. }
+
+    fn read_to_string(&mut self, buf: &mut String) -> ::std::io::Result<usize> { .. }
+}
\ No newline at end of file
@@ -0,0 +1,7 @@
+pub trait Seek {
+    fn seek(&mut self, pos: ::std::io::SeekFrom) -> ::std::io::Result<u64>;
+
+    fn rewind(&mut self) -> ::std::io::Result<()> { .. }
+
+    fn stream_position(&mut self) -> ::std::io::Result<u64> { .. }
+}
\ No newline at end of file
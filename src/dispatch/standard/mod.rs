@@ -1,7 +1,7 @@
 use std::{ops::Deref, str::FromStr};
 
 use proc_macro2::Ident;
-use syn::{parse_str, ItemTrait};
+use syn::{parse_str, ItemTrait, Path};
 
 #[derive(Debug)]
 pub enum StandardTrait {
@@ -30,6 +30,7 @@ pub enum StandardTrait {
     IntoFuture,
     FromIterator,
     FusedIterator,
+    Iterator,
     IntoIterator,
     Product,
     Sum,
@@ -71,6 +72,57 @@ pub enum StandardTrait {
     SliceIndex,
     FromStr,
     ToString,
+    DoubleEndedIterator,
+    ExactSizeIterator,
+    Extend,
+    Hash,
+    Hasher,
+    FmtWrite,
+    Clone,
+    PartialEq,
+    PartialOrd,
+    Ord,
+    StdError,
+    Read,
+    IoWrite,
+    BufRead,
+    Seek,
+}
+
+/// Methods that more than one std trait has, e.g. `Debug::fmt` and
+/// `Display::fmt`. A method call is ambiguous if the dispatched type
+/// implements both traits and both are in scope, so we call these
+/// through the trait path instead.
+const SHARED_METHODS: [&str; 8] = [
+    "fmt",
+    "write",
+    "write_fmt",
+    "eq",
+    "partial_cmp",
+    "cmp",
+    "max",
+    "min",
+];
+
+pub fn is_shared_method(method: &Ident) -> bool {
+    SHARED_METHODS.iter().any(|name| method == name)
+}
+
+/// The `fmt` traits, which can always write nothing, e.g. for a variant
+/// that has nothing to format.
+pub fn is_fmt_trait(path: &Path) -> bool {
+    matches!(
+        StandardTrait::try_from(path),
+        Ok(StandardTrait::Binary
+            | StandardTrait::Debug
+            | StandardTrait::Display
+            | StandardTrait::LowerExp
+            | StandardTrait::LowerHex
+            | StandardTrait::Octal
+            | StandardTrait::Pointer
+            | StandardTrait::UpperExp
+            | StandardTrait::UpperHex)
+    )
 }
 
 #[repr(transparent)]
@@ -106,6 +158,7 @@ impl From<StandardTrait> for TraitSchematic {
                 StandardTrait::IntoFuture => parse_str(include_str!("./IntoFuture.rs")),
                 StandardTrait::FromIterator => parse_str(include_str!("./FromIterator.rs")),
                 StandardTrait::FusedIterator => parse_str(include_str!("./FusedIterator.rs")),
+                StandardTrait::Iterator => parse_str(include_str!("./Iterator.rs")),
                 StandardTrait::IntoIterator => parse_str(include_str!("./IntoIterator.rs")),
                 StandardTrait::Product => parse_str(include_str!("./Product.rs")),
                 StandardTrait::Sum => parse_str(include_str!("./Sum.rs")),
@@ -147,6 +200,25 @@ impl From<StandardTrait> for TraitSchematic {
                 StandardTrait::SliceIndex => parse_str(include_str!("./SliceIndex.rs")),
                 StandardTrait::FromStr => parse_str(include_str!("./FromStr.rs")),
                 StandardTrait::ToString => parse_str(include_str!("./ToString.rs")),
+                StandardTrait::DoubleEndedIterator => {
+                    parse_str(include_str!("./DoubleEndedIterator.rs"))
+                }
+                StandardTrait::ExactSizeIterator => {
+                    parse_str(include_str!("./ExactSizeIterator.rs"))
+                }
+                StandardTrait::Extend => parse_str(include_str!("./Extend.rs")),
+                StandardTrait::Hash => parse_str(include_str!("./Hash.rs")),
+                StandardTrait::Hasher => parse_str(include_str!("./Hasher.rs")),
+                StandardTrait::FmtWrite => parse_str(include_str!("./FmtWrite.rs")),
+                StandardTrait::Clone => parse_str(include_str!("./Clone.rs")),
+                StandardTrait::PartialEq => parse_str(include_str!("./PartialEq.rs")),
+                StandardTrait::PartialOrd => parse_str(include_str!("./PartialOrd.rs")),
+                StandardTrait::Ord => parse_str(include_str!("./Ord.rs")),
+                StandardTrait::StdError => parse_str(include_str!("./Error.rs")),
+                StandardTrait::Read => parse_str(include_str!("./Read.rs")),
+                StandardTrait::IoWrite => parse_str(include_str!("./IoWrite.rs")),
+                StandardTrait::BufRead => parse_str(include_str!("./BufRead.rs")),
+                StandardTrait::Seek => parse_str(include_str!("./Seek.rs")),
             }
             .expect("Std trait file should exist"),
         )
@@ -182,6 +254,7 @@ impl FromStr for StandardTrait {
             "IntoFuture" => Ok(Self::IntoFuture),
             "FromIterator" => Ok(Self::FromIterator),
             "FusedIterator" => Ok(Self::FusedIterator),
+            "Iterator" => Ok(Self::Iterator),
             "IntoIterator" => Ok(Self::IntoIterator),
             "Product" => Ok(Self::Product),
             "Sum" => Ok(Self::Sum),
@@ -223,6 +296,19 @@ impl FromStr for StandardTrait {
             "SliceIndex" => Ok(Self::SliceIndex),
             "FromStr" => Ok(Self::FromStr),
             "ToString" => Ok(Self::ToString),
+            "DoubleEndedIterator" => Ok(Self::DoubleEndedIterator),
+            "ExactSizeIterator" => Ok(Self::ExactSizeIterator),
+            "Extend" => Ok(Self::Extend),
+            "Hash" => Ok(Self::Hash),
+            "Hasher" => Ok(Self::Hasher),
+            "Clone" => Ok(Self::Clone),
+            "PartialEq" => Ok(Self::PartialEq),
+            "PartialOrd" => Ok(Self::PartialOrd),
+            "Ord" => Ok(Self::Ord),
+            "Error" => Ok(Self::StdError),
+            "Read" => Ok(Self::Read),
+            "BufRead" => Ok(Self::BufRead),
+            "Seek" => Ok(Self::Seek),
             _ => Err(()),
         }
     }
@@ -234,6 +320,22 @@ impl TryFrom<&Ident> for StandardTrait {
     }
 }
 
+impl TryFrom<&Path> for StandardTrait {
+    type Error = ();
+    /// Same as for `Ident`, except that `Write` has to be qualified with
+    /// its module, i.e. `fmt::Write` or `io::Write`.
+    fn try_from(value: &Path) -> Result<Self, Self::Error> {
+        let mut segments = value.segments.iter().rev();
+        let last = segments.next().ok_or(())?;
+
+        match segments.next() {
+            Some(module) if last.ident == "Write" && module.ident == "io" => Ok(Self::IoWrite),
+            Some(module) if last.ident == "Write" && module.ident == "fmt" => Ok(Self::FmtWrite),
+            _ => StandardTrait::try_from(&last.ident),
+        }
+    }
+}
+
 impl Deref for TraitSchematic {
     type Target = ItemTrait;
 
@@ -1,12 +1,17 @@
 use proc_macro2::Ident;
 use quote::format_ident;
 use syn::{
-    punctuated::Punctuated, token, BoundLifetimes, Lifetime, Token, TraitBoundModifier, Type,
+    punctuated::Punctuated, token, BoundLifetimes, Expr, GenericArgument, Lifetime, PathArguments,
+    Token, TraitBoundModifier, TraitItem, Type,
 };
 
+mod crates;
 mod parse;
 mod to_tokens;
 
+#[cfg(test)]
+pub use crates::with_crates;
+
 #[derive(Debug)]
 pub struct WhereClause {
     pub where_token: Token![where],
@@ -48,6 +53,90 @@ pub struct TraitBound {
     pub modifier: TraitBoundModifier,
     pub lifetimes: Option<BoundLifetimes>,
     pub ty: Type,
+
+    /// Dispatch options, e.g. `^Iterator(forward = all)`.
+    pub options: Option<(token::Paren, DispatchOptions)>,
+
+    /// An inline trait schematic, used to dispatch traits that cannot be
+    /// tagged with `#[penum]`, e.g. `^Encode { fn encode(&self); }`.
+    pub items: Option<(token::Brace, Vec<TraitItem>)>,
+}
+
+#[derive(Clone, Default, Hash, PartialEq, Eq, Debug)]
+pub struct DispatchOptions {
+    pub forward: Forward,
+    pub mismatch: Mismatch,
+
+    /// Associated types that differ between the dispatched types, and
+    /// are therefore unified into a generated sum type, e.g.
+    /// `^Iterator(sum = [Item])` sets `type Item = EnumItem`.
+    pub sum: Vec<Ident>,
+    pub deref: Deref,
+
+    /// Expressions that are returned instead of the default of a method
+    /// for variants that aren't dispatched, e.g.
+    /// `^Display(fallback(fmt = write!(f, "-")))`.
+    pub fallback: Vec<(Ident, Expr)>,
+}
+
+/// Which methods to forward to the dispatched field. Provided methods
+/// are only forwarded when asked for, as the default implementation
+/// would otherwise be used.
+///
+/// ```text
+/// ^Iterator                           => next
+/// ^Iterator(forward = all)            => next, size_hint, count, last, nth, fold
+/// ^Iterator(forward = [size_hint])    => next, size_hint
+/// ```
+#[derive(Clone, Default, Hash, PartialEq, Eq, Debug)]
+pub enum Forward {
+    #[default]
+    Required,
+    All,
+    Methods(Vec<Ident>),
+}
+
+/// What methods taking `Self` arguments, e.g. `eq(&self, other: &Self)`,
+/// return when `self` and the arguments are different variants.
+///
+/// ```text
+/// ^PartialEq                          => false, the same as any other missing arm
+/// ^PartialOrd(mismatch = order)       => compare the order of the variants
+/// ^Add<Output = Self>(mismatch = panic)
+/// ```
+#[derive(Clone, Copy, Default, Hash, PartialEq, Eq, Debug)]
+pub enum Mismatch {
+    #[default]
+    Default,
+    Order,
+    Panic,
+}
+
+/// Wrapper types that are seen through when matching the dispatched
+/// field, so that `T: ^Trait` matches a `Box<T>` field too. References
+/// are always seen through, and fields that are `None` return the
+/// default of the method.
+///
+/// ```text
+/// ^Trait                              => Box<Expr>: Trait
+/// ^Trait(deref = all)                 => Expr: Trait, through Box, Rc, Arc, Option and &
+/// ^Trait(deref = [Box, MyPtr])        => Expr: Trait, through Box, MyPtr and &
+/// ```
+#[derive(Clone, Default, Hash, PartialEq, Eq, Debug)]
+pub enum Deref {
+    #[default]
+    None,
+    All,
+    Wrappers(Vec<Ident>),
+}
+
+/// A wrapper around the dispatched value, from the outside in.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+pub enum DerefLayer {
+    /// `&T`, `Box<T>`, `Rc<T>`, `Arc<T>` or any other `Deref` type.
+    Deref,
+    /// `Option<T>`
+    Option,
 }
 
 impl TypeParamBound {
@@ -60,7 +149,117 @@ impl TypeParamBound {
     }
 }
 
+impl Forward {
+    pub fn includes(&self, method: &Ident) -> bool {
+        match self {
+            Forward::Required => false,
+            Forward::All => true,
+            Forward::Methods(methods) => methods.contains(method),
+        }
+    }
+}
+
+impl Deref {
+    const WRAPPERS: [&'static str; 4] = ["Box", "Rc", "Arc", "Option"];
+
+    pub fn includes(&self, wrapper: &Ident) -> bool {
+        match self {
+            Deref::None => false,
+            Deref::All => Self::WRAPPERS.iter().any(|name| wrapper == name),
+            Deref::Wrappers(wrappers) => wrappers.contains(wrapper),
+        }
+    }
+
+    /// Strips the wrappers off of a field type, e.g. `Option<Box<Expr>>`
+    /// becomes `Expr` with an `Option` and a `Deref` layer.
+    pub fn unwrap<'a>(&self, mut ty: &'a Type) -> (Vec<DerefLayer>, &'a Type) {
+        let mut layers = vec![];
+
+        if matches!(self, Deref::None) {
+            return (layers, ty);
+        }
+
+        loop {
+            match ty {
+                Type::Group(group) => ty = &group.elem,
+                Type::Paren(paren) => ty = &paren.elem,
+                Type::Reference(reference) => {
+                    layers.push(DerefLayer::Deref);
+                    ty = &reference.elem;
+                }
+                Type::Path(path) if path.qself.is_none() => {
+                    let Some(inner) = path
+                        .path
+                        .segments
+                        .last()
+                        .filter(|segment| self.includes(&segment.ident))
+                        .and_then(|segment| match &segment.arguments {
+                            PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+                                match args.args.first() {
+                                    Some(GenericArgument::Type(inner)) => {
+                                        Some((&segment.ident, inner))
+                                    }
+                                    _ => None,
+                                }
+                            }
+                            _ => None,
+                        })
+                    else {
+                        break;
+                    };
+
+                    layers.push(if inner.0 == "Option" {
+                        DerefLayer::Option
+                    } else {
+                        DerefLayer::Deref
+                    });
+
+                    ty = inner.1;
+                }
+                _ => break,
+            }
+        }
+
+        (layers, ty)
+    }
+}
+
 impl TraitBound {
+    pub fn get_deref(&self) -> Deref {
+        self.options
+            .as_ref()
+            .map(|(_, options)| options.deref.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn get_forward(&self) -> Forward {
+        self.options
+            .as_ref()
+            .map(|(_, options)| options.forward.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn get_mismatch(&self) -> Mismatch {
+        self.options
+            .as_ref()
+            .map(|(_, options)| options.mismatch)
+            .unwrap_or_default()
+    }
+
+    pub fn get_sum(&self) -> Vec<Ident> {
+        self.options
+            .as_ref()
+            .map(|(_, options)| options.sum.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn get_fallbacks(&self) -> Vec<(Ident, Expr)> {
+        self.options
+            .as_ref()
+            .map(|(_, options)| options.fallback.clone())
+            .unwrap_or_default()
+    }
+
     pub fn get_ident(&self) -> Ident {
         if let Type::Path(p) = &self.ty {
             p.path
@@ -73,4 +272,22 @@ impl TraitBound {
             format_ident!("{}", "omg")
         }
     }
+
+    /// Returns the crate of a path qualified trait bound, e.g.
+    /// `other_crate` in `other_crate::Trait`. Paths that are local to
+    /// the current crate return None, and so do paths that start with a
+    /// module, e.g. `net::Handler`, unless they start with `::`.
+    pub fn get_crate_ident(&self) -> Option<&Ident> {
+        let Type::Path(path) = &self.ty else {
+            return None;
+        };
+
+        let segments = &path.path.segments;
+        let first = &segments.first()?.ident;
+
+        (segments.len() > 1
+            && !matches!(first.to_string().as_str(), "crate" | "self" | "super")
+            && (path.path.leading_colon.is_some() || crates::is_extern_crate(first)))
+        .then_some(first)
+    }
 }
@@ -0,0 +1,181 @@
+use std::collections::BTreeSet;
+#[cfg(not(test))]
+use std::env;
+#[cfg(not(test))]
+use std::fs;
+#[cfg(not(test))]
+use std::path::PathBuf;
+#[cfg(not(test))]
+use std::sync::OnceLock;
+
+use proc_macro2::Ident;
+
+/// Crates that are always available, whether they are listed as
+/// dependencies or not.
+const SYSROOT_CRATES: [&str; 5] = ["std", "core", "alloc", "proc_macro", "test"];
+
+/// Tells if `ident` is a crate that the current crate can refer to, i.e.
+/// one of its dependencies, rather than one of its modules. If the
+/// dependencies cannot be read, every name is assumed to be a crate.
+///
+/// ```text
+/// [dependencies]
+/// shapes = ".."               => ^shapes::Shape is imported from `shapes`
+///                             => ^net::Handler waits for `net::Handler` to be tagged
+/// ```
+pub fn is_extern_crate(ident: &Ident) -> bool {
+    SYSROOT_CRATES.iter().any(|krate| ident == krate)
+        || with_dependencies(|dependencies| {
+            dependencies.is_none_or(|dependencies| dependencies.contains(&ident.to_string()))
+        })
+}
+
+#[cfg(not(test))]
+fn with_dependencies<R>(f: impl FnOnce(Option<&BTreeSet<String>>) -> R) -> R {
+    static DEPENDENCIES: OnceLock<Option<BTreeSet<String>>> = OnceLock::new();
+
+    f(DEPENDENCIES.get_or_init(read_dependencies).as_ref())
+}
+
+/// Unit tests assume that every name is a crate, unless they list the
+/// dependencies with `with_crates`.
+#[cfg(test)]
+fn with_dependencies<R>(f: impl FnOnce(Option<&BTreeSet<String>>) -> R) -> R {
+    DEPENDENCIES.with(|dependencies| f(dependencies.borrow().as_ref()))
+}
+
+#[cfg(test)]
+thread_local! {
+    static DEPENDENCIES: std::cell::RefCell<Option<BTreeSet<String>>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Runs `f` as if `crates` were the only dependencies.
+#[cfg(test)]
+pub fn with_crates<R>(crates: &[&str], f: impl FnOnce() -> R) -> R {
+    let crates = crates.iter().map(|krate| krate.to_string()).collect();
+
+    DEPENDENCIES.with(|dependencies| *dependencies.borrow_mut() = Some(crates));
+    let result = f();
+    DEPENDENCIES.with(|dependencies| *dependencies.borrow_mut() = None);
+
+    result
+}
+
+/// Reads the names that the dependencies are used with from the manifest
+/// of the crate that is being compiled. Binaries, examples and tests can
+/// also use the library of their own package.
+#[cfg(not(test))]
+fn read_dependencies() -> Option<BTreeSet<String>> {
+    let manifest = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR")?).join("Cargo.toml");
+    let mut dependencies = parse_dependencies(&fs::read_to_string(manifest).ok()?);
+
+    let package = env::var("CARGO_PKG_NAME").ok()?.replace('-', "_");
+    let is_library = env::var_os("CARGO_BIN_NAME").is_none()
+        && env::var("CARGO_CRATE_NAME").is_ok_and(|krate| krate == package);
+
+    if !is_library {
+        dependencies.insert(package);
+    }
+
+    Some(dependencies)
+}
+
+/// The keys of every dependency table, e.g. `[dependencies]`,
+/// `[dev-dependencies.shapes]` or `[target.'cfg(unix)'.dependencies]`.
+/// Renamed dependencies are used by their key, not their package.
+fn parse_dependencies(manifest: &str) -> BTreeSet<String> {
+    const TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+    let mut dependencies = BTreeSet::new();
+    let mut in_table = false;
+
+    for line in manifest.lines().map(str::trim) {
+        if let Some(header) = line.strip_prefix('[') {
+            let header = header
+                .trim_start_matches('[')
+                .split(']')
+                .next()
+                .unwrap_or("");
+            let mut segments = header.split('.').map(str::trim);
+            let last = header.rsplit('.').next().unwrap_or("").trim();
+
+            in_table = TABLES.contains(&last);
+
+            // `[dependencies.shapes]` and `[target.'cfg(unix)'.dependencies.shapes]`
+            if !in_table {
+                let is_table = |segment: &str| TABLES.contains(&segment);
+                if segments.by_ref().any(is_table) {
+                    if let Some(name) = segments.next() {
+                        dependencies.insert(name.trim_matches('"').replace('-', "_"));
+                    }
+                }
+            }
+
+            continue;
+        }
+
+        if !in_table || line.starts_with('#') {
+            continue;
+        }
+
+        // `shapes = ".."`, `shapes = { .. }` or `shapes.workspace = true`
+        let Some(key) = line.split(['=', '.']).next().map(str::trim) else {
+            continue;
+        };
+
+        if !key.is_empty() {
+            dependencies.insert(key.trim_matches('"').replace('-', "_"));
+        }
+    }
+
+    dependencies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_dependencies;
+
+    #[test]
+    fn dependency_tables() {
+        let manifest = r#"
+            [package]
+            name = "app"
+
+            [dependencies]
+            shapes = { path = "../shapes" }
+            serde-json = "1"
+            "quoted" = "1"
+            workspaced.workspace = true
+            # commented = "1"
+
+            [dependencies.tables]
+            version = "1"
+
+            [dev-dependencies]
+            renamed = { package = "other", version = "1" }
+
+            [target.'cfg(unix)'.dependencies]
+            unix = "1"
+
+            [target.'cfg(unix)'.build-dependencies.build]
+            version = "1"
+
+            [features]
+            default = []
+        "#;
+
+        assert_eq!(
+            parse_dependencies(manifest).into_iter().collect::<Vec<_>>(),
+            [
+                "build",
+                "quoted",
+                "renamed",
+                "serde_json",
+                "shapes",
+                "tables",
+                "unix",
+                "workspaced"
+            ]
+        );
+    }
+}
@@ -1,7 +1,8 @@
-use proc_macro2::Span;
+use proc_macro2::{Span, TokenStream, TokenTree};
 use syn::{
-    parenthesized,
-    parse::{Parse, ParseStream, Result},
+    braced, bracketed, parenthesized,
+    parse::{discouraged::Speculative, Parse, ParseStream, Result},
+    parse2,
     punctuated::Punctuated,
     token, BoundLifetimes, Lifetime, ParenthesizedGenericArguments, PathArguments, Token,
     TraitBoundModifier,
@@ -150,11 +151,14 @@ impl Parse for TraitBound {
         let modifier: TraitBoundModifier = input.parse()?;
         let lifetimes: Option<BoundLifetimes> = input.parse()?;
 
-        let mut ty: Type = input.parse()?;
+        // Without plus, so that `T: ^A + ^B` is parsed as two bounds
+        // instead of one trait object.
+        let (mut ty, options) = parse_type_and_options(input)?;
 
         // FIXME: Should probably look over this again
         if let Type::Path(ref mut path) = ty {
             if path.path.segments.last().unwrap().arguments.is_empty()
+                && options.is_none()
                 && (input.peek(token::Paren) || input.peek(Token![::]) && input.peek3(token::Paren))
             {
                 input.parse::<Option<Token![::]>>()?;
@@ -164,12 +168,226 @@ impl Parse for TraitBound {
             }
         }
 
+        let items = if input.peek(token::Brace) {
+            let content;
+            let brace = braced!(content in input);
+            let mut items = vec![];
+
+            while !content.is_empty() {
+                items.push(content.parse()?);
+            }
+
+            Some((brace, items))
+        } else {
+            None
+        };
+
         Ok(TraitBound {
             paren_token: None,
             dispatch,
             modifier,
             lifetimes,
             ty,
+            options,
+            items,
         })
     }
 }
+
+/// Options look like parenthesized generic arguments, e.g.
+/// `^Iterator(forward = all)` vs `^Fn(i32)`, so we have to split them
+/// from the type before syn gets to parse it.
+fn parse_type_and_options(
+    input: ParseStream,
+) -> Result<(Type, Option<(token::Paren, DispatchOptions)>)> {
+    let fork = input.fork();
+    let mut tokens = TokenStream::new();
+    let mut depth = 0usize;
+    let mut arrow = false;
+
+    while !fork.is_empty() {
+        if depth == 0 {
+            if fork.peek(token::Paren) && DispatchOptions::peek(&fork) {
+                input.advance_to(&fork);
+
+                let content;
+                let paren = parenthesized!(content in input);
+
+                return Ok((parse2(tokens)?, Some((paren, content.parse()?))));
+            }
+
+            if fork.peek(Token![+])
+                || fork.peek(Token![,])
+                || fork.peek(token::Brace)
+                || fork.peek(Token![;])
+            {
+                break;
+            }
+        }
+
+        let tt: TokenTree = fork.parse()?;
+
+        if let TokenTree::Punct(punct) = &tt {
+            match punct.as_char() {
+                '<' => depth += 1,
+                '>' if !arrow => depth = depth.saturating_sub(1),
+                _ => (),
+            }
+            arrow = punct.as_char() == '-';
+        } else {
+            arrow = false;
+        }
+
+        tokens.extend(Some(tt));
+    }
+
+    Ok((Type::without_plus(input)?, None))
+}
+
+impl DispatchOptions {
+    /// Options always start with `option =`, or `fallback(`.
+    fn peek(input: ParseStream) -> bool {
+        fn peek_option(input: ParseStream) -> Result<bool> {
+            let content;
+            parenthesized!(content in input);
+
+            if content.peek(syn::Ident) && content.peek2(token::Paren) {
+                let option: Ident = content.parse()?;
+                return Ok(option == "fallback");
+            }
+
+            Ok(content.peek(syn::Ident) && content.peek2(Token![=]))
+        }
+
+        peek_option(&input.fork()).unwrap_or(false)
+    }
+}
+
+impl Parse for DispatchOptions {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut options = DispatchOptions::default();
+
+        while !input.is_empty() {
+            let option: Ident = input.parse()?;
+
+            if option == "fallback" && input.peek(token::Paren) {
+                let content;
+                parenthesized!(content in input);
+
+                let fallbacks = Punctuated::<Fallback, Token![,]>::parse_terminated(&content)?;
+                options.fallback.extend(
+                    fallbacks
+                        .into_iter()
+                        .map(|Fallback(method, expr)| (method, expr)),
+                );
+            } else {
+                let _: Token![=] = input.parse()?;
+
+                match option.to_string().as_str() {
+                    "forward" => options.forward = input.parse()?,
+                    "mismatch" => options.mismatch = input.parse()?,
+                    "sum" if input.peek(token::Bracket) => {
+                        let content;
+                        bracketed!(content in input);
+
+                        let types = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+                        options.sum = types.into_iter().collect();
+                    }
+                    "sum" => options.sum = vec![input.parse()?],
+                    "deref" => options.deref = input.parse()?,
+                    _ => {
+                        return Err(syn::Error::new(
+                            option.span(),
+                            format!(
+                                "Unknown dispatch option `{option}`, expected `forward`, `mismatch`, `sum`, `deref` or `fallback(..)`."
+                            ),
+                        ))
+                    }
+                }
+            }
+
+            if input.is_empty() {
+                break;
+            }
+
+            let _: Token![,] = input.parse()?;
+        }
+
+        Ok(options)
+    }
+}
+
+/// `method = expr` in `fallback(..)`.
+struct Fallback(Ident, Expr);
+
+impl Parse for Fallback {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let method = input.parse()?;
+        let _: Token![=] = input.parse()?;
+        Ok(Fallback(method, input.parse()?))
+    }
+}
+
+impl Parse for Forward {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(token::Bracket) {
+            let content;
+            bracketed!(content in input);
+
+            let methods = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+
+            return Ok(Forward::Methods(methods.into_iter().collect()));
+        }
+
+        let ident: Ident = input.parse()?;
+
+        match ident.to_string().as_str() {
+            "all" => Ok(Forward::All),
+            "required" => Ok(Forward::Required),
+            _ => Err(syn::Error::new(
+                ident.span(),
+                "Expected `all`, `required` or a list of methods, e.g. `[size_hint, nth]`.",
+            )),
+        }
+    }
+}
+
+impl Parse for Deref {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(token::Bracket) {
+            let content;
+            bracketed!(content in input);
+
+            let wrappers = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+
+            return Ok(Deref::Wrappers(wrappers.into_iter().collect()));
+        }
+
+        let ident: Ident = input.parse()?;
+
+        match ident.to_string().as_str() {
+            "all" => Ok(Deref::All),
+            "none" => Ok(Deref::None),
+            _ => Err(syn::Error::new(
+                ident.span(),
+                "Expected `all`, `none` or a list of wrappers, e.g. `[Box, Rc]`.",
+            )),
+        }
+    }
+}
+
+impl Parse for Mismatch {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident: Ident = input.parse()?;
+
+        match ident.to_string().as_str() {
+            "default" => Ok(Mismatch::Default),
+            "order" => Ok(Mismatch::Order),
+            "panic" => Ok(Mismatch::Panic),
+            _ => Err(syn::Error::new(
+                ident.span(),
+                "Expected `default`, `order` or `panic`.",
+            )),
+        }
+    }
+}
@@ -60,3 +60,47 @@ impl ToTokens for TraitBound {
         }
     }
 }
+
+impl ToTokens for DispatchOptions {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            forward,
+            mismatch,
+            sum,
+            deref,
+            fallback,
+        } = self;
+        let (methods, exprs): (Vec<_>, Vec<_>) = fallback.iter().cloned().unzip();
+        tokens.extend(quote::quote!(forward = #forward, mismatch = #mismatch, sum = [#(#sum),*], deref = #deref, fallback(#(#methods = #exprs),*)));
+    }
+}
+
+impl ToTokens for Mismatch {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            Mismatch::Default => quote::quote!(default),
+            Mismatch::Order => quote::quote!(order),
+            Mismatch::Panic => quote::quote!(panic),
+        })
+    }
+}
+
+impl ToTokens for Deref {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            Deref::None => quote::quote!(none),
+            Deref::All => quote::quote!(all),
+            Deref::Wrappers(wrappers) => quote::quote!([#(#wrappers),*]),
+        })
+    }
+}
+
+impl ToTokens for Forward {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            Forward::Required => quote::quote!(required),
+            Forward::All => quote::quote!(all),
+            Forward::Methods(methods) => quote::quote!([#(#methods),*]),
+        })
+    }
+}
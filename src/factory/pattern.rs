@@ -1,18 +1,22 @@
 use syn::{
     punctuated::{Iter, Punctuated},
-    spanned::Spanned,
     token, ExprRange, Field, Ident, Token,
 };
 
 use quote::ToTokens;
 
 use crate::{
-    dispatch::{Blueprint, BlueprintsMap},
+    dispatch::{Blueprint, BlueprintsMap, LookupError},
     error::Diagnostic,
-    polym::UniqueHashId,
+    utils::{
+        ambiguous_trait, pinned_deref_not_permitted, trait_not_found, unknown_fallback,
+        unknown_forward, unqualified_trait,
+    },
 };
 
-use super::{ComparablePats, PredicateType, WhereClause, WherePredicate};
+use super::{
+    ComparablePats, PredicateType, TraitBound, TypeParamBound, WhereClause, WherePredicate,
+};
 
 mod boilerplate;
 mod parse;
@@ -28,6 +32,8 @@ pub type PunctuatedParameters = Punctuated<PatFieldKind, Token![,]>;
 /// ^^^^^^^^^^^^^^^^^   ^^^^^^^^^^^^^^
 /// <Pattern>           <clause>
 /// ```
+///
+/// The pattern can be preceded by options, e.g. `strict, (T) where ..`.
 #[derive(Default, Debug)]
 pub struct PenumExpr {
     /// Used for matching against incoming variants
@@ -36,6 +42,11 @@ pub struct PenumExpr {
     /// Contains an optional where clause with one or more where
     /// predicates.
     pub clause: Option<WhereClause>,
+
+    /// Set by the `strict` option. Dispatched methods that would have to
+    /// panic for a variant are reported as errors instead, and matches
+    /// that cover every variant have no `_` arm.
+    pub strict: bool,
 }
 
 /// Pattern fragments are used as constituents for the Penum expression composite type.
@@ -108,6 +119,16 @@ pub enum PatFieldKind {
     /// optional.
     Field(Field),
 
+    /// Same as `Field`, but prefixed with a `^` marker. It's used to
+    /// explicitly select what field should be dispatched when a variant
+    /// has more than one candidate.
+    ///
+    /// ```text
+    /// (_, ^T, U) | { a: U, b: ^T }
+    ///     ^^            ^^^^^^^^
+    /// ```
+    Dispatch(Token![^], Field),
+
     /// We use this to represent that we don't care amount the left over
     /// arguments.
     ///
@@ -155,57 +176,126 @@ impl PenumExpr {
         self.clause.is_some()
     }
 
-    /// This should probably be refactored...
+    /// Collect a blueprint for each dispatchable trait bound found in
+    /// our where clause.
     ///
-    /// NOTE: This totally works when we are using Generics with patterns. But if we use
-    /// concrete types with trait bounds it breaks.
-    ///
-    /// We somehow need to know that if a penum expression contains two or more concrete types with
-    /// the same dispatched trait bound we should interprete them as the same. If we do not do this
-    /// we create x implementations for the same trait.
-    ///
-    /// I feel like the blueprints map key should be based on trait bound instead of type.
-    ///
-    /// SOLUTION: We could keep this as it is, and instead fold our blueprints map so that types with the
-    /// same trait bounds are combined.
+    /// Blueprints are keyed by trait bound, so if a penum expression
+    /// contains two or more types with the same dispatched trait bound,
+    /// they will share the same blueprint and end up in the same impl.
+    /// ```text
+    /// _ where i32: ^Trait, usize: ^Trait => impl Trait for Enum { .. }
+    /// ```
     pub fn get_blueprints_map(&self, error: &Diagnostic) -> Option<BlueprintsMap> {
-        let Some(clause) = self.clause.as_ref() else {
-            return None;
-        };
+        let clause = self.clause.as_ref()?;
 
         let mut polymap = BlueprintsMap::default();
+        let mut supertraits = vec![];
 
         for pred in clause.predicates.iter() {
             if let WherePredicate::Type(pred_ty) = pred {
-                let mut blueprints = Vec::<Blueprint>::default();
-
                 for param_bound in pred_ty.bounds.iter() {
                     // Only get trait bound with `^` caret. e.g Type: ^Trait
                     if let Some(trait_bound) = param_bound.get_dispatchable_trait_bound() {
                         // This will try to first check if the trait exists in our
-                        // std trait store, and if it's not found, we'll check our
-                        // SHM map.
+                        // SHM map, and if it's not found, we'll check our std
+                        // trait store. A bare name that matches traits in different
+                        // modules is an error. If it's still not found, we assume
+                        // that the trait will be tagged later on, or that it has
+                        // been exported by another crate.
                         match Blueprint::try_from(trait_bound) {
-                            Ok(blueprint) => blueprints.push(blueprint),
-                            Err(err) => error.extend(trait_bound.span(), err),
+                            Ok(blueprint) => {
+                                for method in blueprint.get_unknown_forwards() {
+                                    error.extend_spanned(
+                                        &method,
+                                        unknown_forward(&method, &trait_bound.get_ident()),
+                                    );
+                                }
+
+                                for method in blueprint.get_unknown_fallbacks() {
+                                    error.extend_spanned(
+                                        &method,
+                                        unknown_fallback(&method, &trait_bound.get_ident()),
+                                    );
+                                }
+
+                                for (assoc, message) in blueprint.get_sum_errors() {
+                                    error.extend_spanned(&assoc, message);
+                                }
+
+                                if let Some(method) = blueprint.get_pinned_deref() {
+                                    error.extend_spanned(
+                                        trait_bound,
+                                        pinned_deref_not_permitted(
+                                            &method,
+                                            &trait_bound.get_ident(),
+                                        ),
+                                    );
+                                    continue;
+                                }
+
+                                supertraits.push((
+                                    &pred_ty.bounded_ty,
+                                    trait_bound,
+                                    blueprint.get_supertraits(),
+                                ));
+                                polymap.insert(&pred_ty.bounded_ty, blueprint);
+                            }
+                            Err(LookupError::Ambiguous(candidates)) => error.extend_spanned(
+                                trait_bound,
+                                ambiguous_trait(&trait_bound.get_ident(), &candidates),
+                            ),
+                            Err(LookupError::Unqualified) => error.extend_spanned(
+                                trait_bound,
+                                unqualified_trait(&trait_bound.get_ident()),
+                            ),
+                            // Traits in `std` won't be registered later on.
+                            Err(_)
+                                if trait_bound.get_crate_ident().is_some_and(|ident| {
+                                    ident == "std" || ident == "core" || ident == "alloc"
+                                }) =>
+                            {
+                                error.extend_spanned(
+                                    trait_bound,
+                                    trait_not_found(&trait_bound.get_ident()),
+                                )
+                            }
+                            Err(_) if trait_bound.get_crate_ident().is_some() => {
+                                polymap.import(trait_bound)
+                            }
+                            Err(_) => polymap.defer(trait_bound),
                         }
                     }
                 }
+            }
+        }
 
-                if blueprints.is_empty() {
-                    return None;
-                }
+        // Supertraits are registered after all explicit bounds, so that
+        // e.g. `T: ^DerefMut + ^Deref<Target = str>` keeps its binding.
+        for (bounded_ty, trait_bound, bounds) in supertraits {
+            polymap.insert_supertraits(bounded_ty, trait_bound, bounds, error);
+        }
+
+        (!polymap.is_empty()).then_some(polymap)
+    }
 
-                let ty = UniqueHashId(pred_ty.bounded_ty.clone());
+    /// Remove the dispatch marker from every trait bound that doesn't
+    /// satisfy `f`.
+    pub fn retain_dispatch(&mut self, mut f: impl FnMut(&TraitBound) -> bool) {
+        let Some(clause) = self.clause.as_mut() else {
+            return;
+        };
 
-                if let Some(entry) = polymap.get_mut(&ty) {
-                    entry.append(&mut blueprints);
-                } else {
-                    polymap.insert(ty, blueprints);
+        for pred in clause.predicates.iter_mut() {
+            if let WherePredicate::Type(pred_ty) = pred {
+                for param_bound in pred_ty.bounds.iter_mut() {
+                    if let TypeParamBound::Trait(trait_bound) = param_bound {
+                        if trait_bound.dispatch.is_some() && !f(trait_bound) {
+                            trait_bound.dispatch = None;
+                        }
+                    }
                 }
             }
         }
-        (!polymap.is_empty()).then_some(polymap)
     }
 
     pub fn find_predicate(
@@ -232,7 +322,12 @@ impl PatFieldKind {
     /// This is useful when we just want to check if we should care
     /// about checking the inner structure of PatParamKind.
     pub fn is_field(&self) -> bool {
-        matches!(self, PatFieldKind::Field(_))
+        matches!(self, PatFieldKind::Field(_) | PatFieldKind::Dispatch(..))
+    }
+
+    /// Used to check if a field has been marked for dispatch with `^`.
+    pub fn is_dispatch(&self) -> bool {
+        matches!(self, PatFieldKind::Dispatch(..))
     }
 
     /// Used in ComparablePair method calls to check if a parameter is
@@ -256,7 +351,7 @@ impl PatFieldKind {
     /// field.
     pub fn get_field(&self) -> Option<&Field> {
         match self {
-            PatFieldKind::Field(field) => Some(field),
+            PatFieldKind::Field(field) | PatFieldKind::Dispatch(_, field) => Some(field),
             _ => None,
         }
     }
@@ -60,9 +60,20 @@ impl ImplExpr {
 
         let mut bounds = TokenStream::new();
 
+        // Options and inline trait schematics aren't part of the bound tokens.
+        let options = trait_bound
+            .options
+            .as_ref()
+            .map(|(_, options)| quote::quote!((#options)));
+
+        let items = trait_bound
+            .items
+            .as_ref()
+            .map(|(_, items)| quote::quote!({ #(#items)* }));
+
         for (index, ty) in tys.iter().enumerate() {
             // Always dispatch for impl expressions
-            bounds.extend(quote::quote!(#ty: ^#trait_bound));
+            bounds.extend(quote::quote!(#ty: ^#trait_bound #options #items));
 
             if index != tys.len() - 1 {
                 bounds.extend(quote::quote!(,));
@@ -75,6 +86,23 @@ impl ImplExpr {
 
 impl Parse for PenumExpr {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        // Options always end with a comma, which a pattern never starts with.
+        if input.peek(Ident) && input.peek2(Token![,]) {
+            let option: Ident = input.parse()?;
+            let _: Token![,] = input.parse()?;
+
+            if option != "strict" {
+                return Err(syn::Error::new(
+                    option.span(),
+                    format!("Unknown option `{option}`, expected `strict`."),
+                ));
+            }
+
+            let mut penum: PenumExpr = input.parse()?;
+            penum.strict = true;
+            return Ok(penum);
+        }
+
         if input.peek(LitStr) {
             let pat: LitStr = input.parse()?;
             let penum: PenumExpr = pat.parse_with(PenumExpr::parse)?;
@@ -89,6 +117,7 @@ impl Parse for PenumExpr {
                         group: PatComposite::Inferred,
                     }],
                     clause: Some(input.parse::<ImplExpr>()?.into_clause()),
+                    strict: false,
                 });
             }
 
@@ -98,6 +127,7 @@ impl Parse for PenumExpr {
                     group: PatComposite::Inferred,
                 }],
                 clause: Some(input.parse()?),
+                strict: false,
             });
         }
 
@@ -119,6 +149,7 @@ impl Parse for PenumExpr {
                     None
                 }
             },
+            strict: false,
         })
     }
 }
@@ -150,6 +181,18 @@ impl Parse for PatFieldKind {
             PatFieldKind::Range(input.parse()?)
         } else if input.peek(Token![..]) {
             PatFieldKind::Variadic(input.parse()?)
+        } else if input.peek(Token![^]) {
+            PatFieldKind::Dispatch(input.parse()?, input.call(Field::parse_unnamed)?)
+        } else if input.peek(Ident) && input.peek2(Token![:]) && input.peek3(Token![^]) {
+            let ident: Ident = input.parse()?;
+            let colon_token: Token![:] = input.parse()?;
+            let caret: Token![^] = input.parse()?;
+            let mut field = input.call(Field::parse_unnamed)?;
+
+            field.ident = Some(ident);
+            field.colon_token = Some(colon_token);
+
+            PatFieldKind::Dispatch(caret, field)
         } else if input.peek(Ident) && input.peek2(Token![:]) {
             PatFieldKind::Field(input.call(Field::parse_named)?)
         } else {
@@ -16,6 +16,12 @@ impl ToTokens for PatFieldKind {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         match self {
             PatFieldKind::Field(f) => f.to_tokens(tokens),
+            PatFieldKind::Dispatch(caret, f) => {
+                f.ident.to_tokens(tokens);
+                f.colon_token.to_tokens(tokens);
+                caret.to_tokens(tokens);
+                f.ty.to_tokens(tokens);
+            }
             PatFieldKind::Variadic(v) => v.to_tokens(tokens),
             PatFieldKind::Range(r) => r.to_tokens(tokens),
             PatFieldKind::Infer => tokens.extend(TokenStream::from_str("_")),
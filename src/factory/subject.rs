@@ -5,11 +5,13 @@ use quote::{format_ident, ToTokens};
 use syn::{
     punctuated::Punctuated,
     token::{self, Comma},
-    Attribute, DataEnum, Expr, ExprMacro, Fields, Generics, Macro, Token, TraitBound, Variant,
-    Visibility,
+    Attribute, DataEnum, Expr, ExprMacro, Fields, Generics, Macro, Path, Token, TraitBound,
+    Variant, Visibility,
 };
 
-use crate::utils::{Stringify, ABSTRACT_MACRO_EXPR_SYMBOL, DEFAULT_VARIANT_SYMBOL};
+use crate::utils::{
+    is_arm_attr, is_helper_attr, Stringify, ABSTRACT_MACRO_EXPR_SYMBOL, DEFAULT_VARIANT_SYMBOL,
+};
 
 use super::Comparable;
 
@@ -34,6 +36,22 @@ pub struct AbstractExpr {
     pub expr: Expr,
 }
 
+/// A hand-written arm of a dispatched method, which is used instead of
+/// the dispatched call for the variant it's attached to.
+///
+/// ```text
+/// #[penum(arm(Display::fmt = write!(f, "none")))]
+///             ^^^^^^^  ^^^   ^^^^^^^^^^^^^^^^^^
+///             <trait>  <method>  <expr>
+/// ```
+#[derive(Clone, Debug)]
+pub struct ArmOverride {
+    pub trait_path: Path,
+    pub method: Ident,
+    pub eq_token: Token![=],
+    pub expr: Expr,
+}
+
 #[derive(Clone, Debug)]
 pub struct DiscriminantImpl {
     composite: Punctuated<AbstractExpr, Token![,]>,
@@ -45,6 +63,34 @@ impl Subject {
         &self.data.variants
     }
 
+    /// Remove our `#[penum(..)]` helper attributes from variants and
+    /// their fields. These are only meant for us, and would otherwise
+    /// be interpreted as another `penum` invocation.
+    pub fn strip_helper_attrs(&mut self) {
+        for variant in self.data.variants.iter_mut() {
+            variant.attrs.retain(|attr| !is_helper_attr(attr));
+
+            for field in variant.fields.iter_mut() {
+                field.attrs.retain(|attr| !is_helper_attr(attr));
+            }
+        }
+    }
+
+    /// Collects every `#[penum(arm(..))]` override, together with the
+    /// variant it's attached to.
+    pub fn get_arm_overrides(&self) -> syn::Result<Vec<(Ident, ArmOverride)>> {
+        let mut overrides = vec![];
+
+        for variant in self.get_variants() {
+            for attr in variant.attrs.iter().filter(|attr| is_arm_attr(attr)) {
+                let arms = attr.parse_args_with(ArmOverride::parse_attr_args)?;
+                overrides.extend(arms.into_iter().map(|arm| (variant.ident.clone(), arm)));
+            }
+        }
+
+        Ok(overrides)
+    }
+
     /// This will basically break each variant into two parts, VariantIdent and a Comparable. A
     /// Comparable will eventually pair up with another Comparable to create a ComparablePair.
     ///
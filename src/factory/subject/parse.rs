@@ -1,12 +1,12 @@
 use proc_macro2::Ident;
 use syn::{
-    braced,
+    braced, parenthesized,
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
-    token, Attribute, DataEnum, Generics, Token, Variant, Visibility, WhereClause,
+    token, Attribute, DataEnum, Generics, Path, Token, Variant, Visibility, WhereClause,
 };
 
-use super::{AbstractExpr, DiscriminantImpl, Subject};
+use super::{AbstractExpr, ArmOverride, DiscriminantImpl, Subject};
 
 impl Parse for Subject {
     fn parse(input: ParseStream) -> syn::Result<Self> {
@@ -44,6 +44,43 @@ impl Parse for Subject {
     }
 }
 
+impl Parse for ArmOverride {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path: Path = input.parse()?;
+
+        // The last segment is the method, e.g. `fmt` in `Display::fmt`.
+        let mut segments = path.segments.into_iter().collect::<Vec<_>>();
+
+        let (Some(method), false) = (segments.pop(), segments.is_empty()) else {
+            return Err(input.error("Expected a trait method, e.g. `Display::fmt`."));
+        };
+
+        Ok(Self {
+            trait_path: Path {
+                leading_colon: path.leading_colon,
+                segments: segments.into_iter().collect(),
+            },
+            method: method.ident,
+            eq_token: input.parse()?,
+            expr: input.parse()?,
+        })
+    }
+}
+
+impl ArmOverride {
+    /// `arm(Trait::method = expr, ..)`
+    pub fn parse_attr_args(input: ParseStream) -> syn::Result<Vec<Self>> {
+        let _: Ident = input.parse()?;
+
+        let content;
+        parenthesized!(content in input);
+
+        let arms = Punctuated::<ArmOverride, Token![,]>::parse_terminated(&content)?;
+
+        Ok(arms.into_iter().collect())
+    }
+}
+
 impl Parse for AbstractExpr {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         Ok(Self {
@@ -74,6 +74,14 @@ pub fn penum(attr: TokenStream, input: TokenStream) -> TokenStream {
     services::penum_expand(attr, input)
 }
 
+/// Used by the macro that `#[penum]` exports together with a trait, so
+/// that enums in other crates can dispatch it with `^other_crate::Trait`.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __dispatch(input: TokenStream) -> TokenStream {
+    services::dispatch_expand(input)
+}
+
 /// Use this to express how `ToString` should be implemented through variants descriminant.
 ///
 /// # Example
@@ -181,6 +189,35 @@ pub fn static_str(_: TokenStream, input: TokenStream) -> TokenStream {
     services::static_str(input)
 }
 
+/// Use this to register a const as the fallback of its type. Dispatched
+/// methods returning the type, or a reference to it, return the const for
+/// variants that aren't dispatched. It has to come before the enums that
+/// use it, and be in scope where they are declared, unless the module it
+/// is in is given with `path = ..`.
+///
+/// # Example
+///
+/// ```rust
+/// #[penum::fallback]
+/// const STATUS_DEFAULT: Status = Status::Unknown;
+///
+/// #[penum( (T) | unit where T: ^Health )]
+/// enum Service {
+///     Db(Db),
+///     Offline,
+/// }
+/// assert_eq!(Service::Offline.status(), Status::Unknown);
+///
+/// mod model {
+///     #[penum::fallback(path = model)]
+///     pub const STATUS_DEFAULT: Status = Status::Unknown;
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn fallback(attr: TokenStream, input: TokenStream) -> TokenStream {
+    services::fallback_expand(attr, input)
+}
+
 /// Use this when you want to be able to associate a ...
 /// UNDER DEVELOPMENT
 /// # Example
@@ -1,4 +1,5 @@
 use std::borrow::Borrow;
+use std::collections::BTreeSet;
 use std::marker::PhantomData;
 
 use proc_macro::TokenStream;
@@ -10,26 +11,39 @@ use syn::punctuated::Punctuated;
 use syn::token::Add;
 use syn::token::Comma;
 use syn::Ident;
+use syn::Item;
 use syn::ItemImpl;
 
 use syn::parse_quote;
 use syn::spanned::Spanned;
+use syn::visit_mut::VisitMut;
 use syn::Error;
 use syn::Type;
 use syn::TypeParamBound;
 
+use crate::factory;
 use crate::factory::Comparable;
 use crate::factory::PenumExpr;
 use crate::factory::Subject;
+use crate::factory::TraitBound;
 use crate::factory::WherePredicate;
 
+use crate::dispatch::import_trait;
+use crate::dispatch::pin_projection_guards;
+use crate::dispatch::trait_not_found_guards;
+use crate::dispatch::GeneratedType;
+use crate::dispatch::MonomorphizeSelf;
+use crate::dispatch::PendingDispatch;
 use crate::dispatch::VariantSig;
 use crate::error::Diagnostic;
 
 use crate::utils::create_unique_ident;
+use crate::utils::generated_bound_not_dispatched;
 use crate::utils::lifetime_not_permitted;
 use crate::utils::maybe_bounds_not_permitted;
+use crate::utils::missing_dispatch_arms;
 use crate::utils::no_match_found;
+use crate::utils::unknown_arm_trait;
 use crate::utils::Stringify;
 use crate::utils::TraitBoundUtils;
 use crate::utils::TypeUtils;
@@ -59,8 +73,19 @@ pub struct Penum<State = Unassembled> {
     /// I use this to map generics to concrete types that I then can use during substitution stage.
     types: PolyMap,
 
-    /// Contains all the impls that we've managed to construct.
-    impls: Vec<ItemImpl>,
+    /// Contains all the impls that we've managed to construct, and the
+    /// items they need, e.g. sum types of associated types.
+    impls: Vec<Item>,
+
+    /// Trait bounds that have been implemented for the enum, including
+    /// those from an earlier expansion when resuming a pending dispatch.
+    dispatched: BTreeSet<String>,
+
+    /// Dispatched traits that haven't been registered yet.
+    pending: Vec<TraitBound>,
+
+    /// Dispatched traits from other crates.
+    foreign: Vec<TraitBound>,
 
     /// Only used as a DX marker that seperates methods between Disassembled <> Assembled.
     _marker: PhantomData<State>,
@@ -77,10 +102,20 @@ impl Penum<Unassembled> {
             error: Default::default(),
             types: Default::default(),
             impls: Default::default(),
+            dispatched: Default::default(),
+            pending: Default::default(),
+            foreign: Default::default(),
             _marker: Default::default(),
         }
     }
 
+    /// Skip implementing trait bounds that have already been
+    /// implemented, see `PendingDispatch`.
+    pub fn with_dispatched(mut self, dispatched: BTreeSet<String>) -> Self {
+        self.dispatched = dispatched;
+        self
+    }
+
     fn transmute_to_assembled(self) -> Penum<Assembled> {
         // SAFETY: Transmuting self into self with a different zero-sized marker.
         // Since State is a PhantomData, this is safe.
@@ -125,6 +160,11 @@ impl Penum<Unassembled> {
         // self.expr.has_clause(); Turn into iterator instead?
         let mut opt_blueprints = self.expr.get_blueprints_map(self.error.borrow());
 
+        if let Some(blueprints) = opt_blueprints.as_ref() {
+            self.pending = blueprints.get_pending().to_vec();
+            self.foreign = blueprints.get_foreign().to_vec();
+        }
+
         // For each variant:
         // 1. Validate its shape by comparing discriminant and
         //    unit/tuple/struct arity. (OUTER)
@@ -167,6 +207,10 @@ impl Penum<Unassembled> {
             //  Maybe it's something that would be worth having considering something like this:
             //  `_ where String: ^AsRef<str>`
 
+            if let Some(blueprints) = opt_blueprints.as_mut() {
+                blueprints.attach_variant(variant_ident);
+            }
+
             // 1. Check if we match in `shape`
             let Some(matched_pair) = comparable_pats.compare(&comparable_item) else {
                 self.report_invalid_shape(&comparable_item, variant_ident, &pattern_fmt);
@@ -176,10 +220,13 @@ impl Penum<Unassembled> {
             // No support for empty unit iter, yet...
             // NOTE: Make sure to handle composite::unit iterator before removing this
             if matched_pair.as_composite().is_unit() {
+                if let Some(blueprints) = opt_blueprints.as_mut() {
+                    blueprints.attach_unit_variant(variant_ident);
+                }
                 continue;
             }
 
-            let arity = comparable_item.inner.len();
+            let fields = comparable_item.inner;
 
             // 2. Check if we match in `structure`. (We are naively
             // always expecting to never have infixed variadics)
@@ -193,7 +240,7 @@ impl Penum<Unassembled> {
                             variant_ident,
                             field_item,
                             field_index,
-                            arity,
+                            fields,
                             &item_ty_unique,
                         );
                     });
@@ -239,7 +286,8 @@ impl Penum<Unassembled> {
                 let pat_ty_unique = pat_field.ty.get_unique_id();
 
                 let variant_sig =
-                    VariantSig::new(enum_ident, variant_ident, field_item, field_index, arity);
+                    VariantSig::new(enum_ident, variant_ident, field_item, field_index, fields)
+                        .with_marker(param_pattern.is_dispatch());
 
                 // Check if it's a generic or concrete type
                 // - We only accept `_|[A-Z][A-Z0-9]*` as generics.
@@ -332,31 +380,183 @@ impl Penum<Unassembled> {
             }
         }
 
+        // Hand-written arms replace the dispatched arms of their variants.
+        match self.subject.get_arm_overrides() {
+            Ok(overrides) => {
+                for (variant, arm) in overrides.iter() {
+                    let attached = opt_blueprints.as_mut().is_some_and(|blueprints| {
+                        blueprints.attach_arm_override(variant, arm, &self.error)
+                    });
+
+                    if !attached {
+                        self.error.extend_spanned(
+                            &arm.trait_path,
+                            unknown_arm_trait(&arm.trait_path, variant),
+                        );
+                    }
+                }
+            }
+            Err(err) => self.error.extend(err.span(), err),
+        }
+
+        // Select which field to dispatch for each variant.
+        if let Some(blueprints) = opt_blueprints.as_mut() {
+            blueprints.arbitrate(&self.error);
+        }
+
         // Assemble all our impl statements
         opt_blueprints.map(|blueprints| {
             let (impl_generics, ty_generics, where_clause) =
                 &self.subject.generics.split_for_impl();
 
+            // Dispatched types that are pinned by `self: Pin<&mut Self>`.
+            let mut pinned = vec![];
+
             blueprints.for_each_blueprint(|blueprint| {
+                if !self
+                    .dispatched
+                    .insert(blueprint.get_bound_id().get_string())
+                {
+                    return;
+                }
+
+                for ty in blueprint.get_pinned_types() {
+                    if !pinned.contains(&ty) {
+                        pinned.push(ty);
+                    }
+                }
+
+                // Strict mode doesn't allow methods to panic because a
+                // variant has nothing to dispatch to.
+                if self.expr.strict {
+                    let missing = blueprint.get_missing_arms();
+
+                    if !missing.is_empty() {
+                        self.error.extend_spanned(
+                            &*blueprint.bound,
+                            missing_dispatch_arms(&blueprint.bound.get_ident(), &missing),
+                        );
+                    }
+                }
+
+                let strict = self.expr.strict;
                 let trait_path = blueprint.get_sanatized_impl_path();
-                let assoc_methods = blueprint.get_associated_methods();
+                let assoc_methods = blueprint.get_associated_methods(strict);
+
+                // `unsafe trait` needs an `unsafe impl`. The contract of the
+                // trait is upheld by the dispatched types, which implement it.
+                let unsafety = blueprint.schematic.unsafety;
+
+                let generated = blueprint.get_generated_types(&self.subject);
 
                 let assoc_types = blueprint.get_mapped_bindings().map(|bind| {
                     bind.iter()
-                        .map(|b| b.to_token_stream())
+                        .map(|b| {
+                            match generated.iter().find(|g| g.assoc.as_ref() == Some(&b.ident)) {
+                                Some(GeneratedType { item, .. }) => {
+                                    let (assoc, ident) = (&b.ident, &item.ident);
+                                    quote::quote!(type #assoc = #ident #ty_generics;)
+                                }
+                                None => b.to_token_stream(),
+                            }
+                        })
                         .collect::<TokenStream2>()
                 });
 
+                for generated in generated {
+                    let GeneratedType { item, source, .. } = &generated;
+
+                    if generated.bounds.is_empty() {
+                        self.impls.push(Item::Enum(item.clone()));
+                        continue;
+                    }
+
+                    // The traits are dispatched on the generated type the
+                    // same way they are on the enum.
+                    let bounds = generated.get_dispatch_bounds();
+                    let expr = parse_quote!( (T) | unit where T: #(#bounds)+* );
+                    let subject = parse_quote!(#item);
+                    let penum = Penum::new(expr, subject).assemble();
+
+                    // Nothing resumes the dispatch on a generated type, so
+                    // its traits have to be known right away.
+                    for bound in penum.get_undispatched_bounds() {
+                        self.error.extend_spanned(
+                            &*blueprint.bound,
+                            generated_bound_not_dispatched(
+                                &bound.get_ident(),
+                                source,
+                                &blueprint.bound.get_ident(),
+                            ),
+                        );
+                    }
+
+                    self.impls.push(Item::Verbatim(penum.get_tokenstream()));
+                }
+
+                let const_accessors = blueprint.get_const_accessors(&self.subject.vis, strict);
+
+                if !const_accessors.is_empty() {
+                    self.impls.push(Item::Impl(parse_quote!(
+                        impl #impl_generics #enum_ident #ty_generics #where_clause {
+                            #(#const_accessors)*
+                        }
+                    )));
+                }
+
+                // The `Fn*` traits can only be implemented on nightly.
+                if blueprint.is_callable() {
+                    let vis = &self.subject.vis;
+                    let adapter = blueprint.get_callable_adapter(vis);
+
+                    self.impls.push(Item::Impl(parse_quote!(
+                        impl #impl_generics #enum_ident #ty_generics #where_clause {
+                            #(#vis #assoc_methods)*
+
+                            #adapter
+                        }
+                    )));
+
+                    return;
+                }
+
+                // The enum can't have one value for every variant, so the
+                // trait can't be implemented with required consts. The
+                // methods are dispatched as inherent methods instead, next
+                // to the accessors.
+                if blueprint.has_required_consts() {
+                    let inherent_methods = blueprint.get_inherent_methods(strict);
+
+                    if !inherent_methods.is_empty() {
+                        let vis = &self.subject.vis;
+
+                        self.impls.push(Item::Impl(parse_quote!(
+                            impl #impl_generics #enum_ident #ty_generics #where_clause {
+                                #(#vis #inherent_methods)*
+                            }
+                        )));
+                    }
+
+                    return;
+                }
+
                 let implementation: ItemImpl = parse_quote!(
-                    impl #impl_generics #trait_path for #enum_ident #ty_generics #where_clause {
+                    #unsafety impl #impl_generics #trait_path for #enum_ident #ty_generics #where_clause {
                         #assoc_types
 
                         #(#assoc_methods)*
                     }
                 );
 
-                self.impls.push(implementation);
+                self.impls.push(Item::Impl(implementation));
             });
+
+            if !pinned.is_empty() {
+                self.impls.push(Item::Verbatim(pin_projection_guards(
+                    &self.subject,
+                    &pinned,
+                )));
+            }
         });
 
         self.update_where_clause(&predicates);
@@ -425,8 +625,6 @@ impl Penum<Unassembled> {
 }
 
 impl Penum<Assembled> {
-    // NOTE: This is only used for unit tests
-    #[allow(dead_code)]
     pub fn get_tokenstream(self) -> TokenStream2 {
         let (subject, impls, diagnostic) = self.attach_assertions();
 
@@ -438,15 +636,65 @@ impl Penum<Assembled> {
     }
 
     pub fn unwrap_or_error(self) -> TokenStream {
+        let pending = self.pending.clone();
         let (subject, impls, diagnostic) = self.attach_assertions();
 
+        // Traits that are still pending at the end of the crate were never
+        // tagged with `#[penum]`.
+        let guards = trait_not_found_guards(&subject, &pending);
+
         diagnostic
             .map(Error::to_compile_error)
-            .unwrap_or_else(|| quote::quote!(#subject #(#impls)*))
+            .unwrap_or_else(|| quote::quote!(#subject #(#impls)* #guards))
             .into()
     }
 
-    pub(self) fn attach_assertions(mut self) -> (Subject, Vec<ItemImpl>, Diagnostic) {
+    /// Only emits the impls and their items, which is used when resuming a pending
+    /// dispatch where the enum itself has already been emitted.
+    pub fn unwrap_impls_or_error(self) -> TokenStream2 {
+        let (_, impls, diagnostic) = self.attach_assertions();
+
+        diagnostic
+            .map(Error::to_compile_error)
+            .unwrap_or_else(|| quote::quote!(#(#impls)*))
+    }
+
+    /// Returns a dispatch request that should be resumed when the
+    /// pending traits get registered. Nothing is postponed if we already
+    /// have errors to report.
+    pub fn get_pending_dispatch(&self, expr: String) -> Option<PendingDispatch> {
+        (!self.pending.is_empty() && !self.error.has_error()).then(|| PendingDispatch {
+            expr,
+            waiting: self
+                .pending
+                .iter()
+                .map(|bound| (bound.get_ident().to_string(), bound.ty.get_string()))
+                .collect(),
+            dispatched: self.dispatched.clone(),
+        })
+    }
+
+    /// Dispatched traits that are either pending or from other crates.
+    pub fn get_undispatched_bounds(&self) -> impl Iterator<Item = &TraitBound> {
+        self.pending.iter().chain(self.foreign.iter())
+    }
+
+    /// Returns the macro calls that import the traits from other crates,
+    /// which in turn resume the dispatch. See `import_trait`.
+    pub fn get_foreign_imports(&self, expr: &TokenStream2, subject: &TokenStream2) -> TokenStream2 {
+        if self.error.has_error() {
+            return TokenStream2::new();
+        }
+
+        self.foreign
+            .iter()
+            .map(|bound| import_trait(bound, expr, subject, &self.dispatched))
+            .collect()
+    }
+
+    pub(self) fn attach_assertions(mut self) -> (Subject, Vec<Item>, Diagnostic) {
+        self.subject.strip_helper_attrs();
+
         if let Some(where_cl) = self.expr.clause.as_ref() {
             for predicate in where_cl.predicates.iter() {
                 match predicate {
@@ -457,26 +705,62 @@ impl Penum<Assembled> {
                             for ty_id in pty_set.iter() {
                                 let ty = &**ty_id;
 
-                                // Could remove this.
-                                let spanned_bounds = pred
-                                    .bounds
-                                    .to_token_stream()
-                                    .into_iter()
-                                    .map(|mut token| {
-                                        // NOTE: This is the only way we can
-                                        // impose a new span for a `bound`..
-                                        // FIXES: tests/ui/placeholder_with_bound.rs
-                                        // FIXES: tests/ui/trait-bound-not-satisfied.rs
-                                        token.set_span(ty.span());
-                                        token
-                                    })
-                                    .collect::<TokenStream2>();
-
-                                self.subject
-                                    .generics
-                                    .make_where_clause()
-                                    .predicates
-                                    .push(parse_quote! {#ty: #spanned_bounds})
+                                // Bounds that see through wrappers are asserted
+                                // on the wrapped type instead, e.g. `Expr: Trait`
+                                // for a `Box<Expr>` field.
+                                let mut targets: Vec<(&Type, Vec<_>)> = vec![];
+
+                                for bound in pred.bounds.iter() {
+                                    let target = match bound {
+                                        factory::TypeParamBound::Trait(tb) => {
+                                            tb.get_deref().unwrap(ty).1
+                                        }
+                                        _ => ty,
+                                    };
+
+                                    match targets.iter_mut().find(|(ty, _)| *ty == target) {
+                                        Some((_, bounds)) => bounds.push(bound),
+                                        None => targets.push((target, vec![bound])),
+                                    }
+                                }
+
+                                for (ty, bounds) in targets {
+                                    let mut bounds: Punctuated<TypeParamBound, Add> =
+                                        parse_quote!(#(#bounds)+*);
+
+                                    bounds.iter_mut().for_each(|bound| {
+                                        MonomorphizeSelf(ty).visit_type_param_bound_mut(bound)
+                                    });
+
+                                    // Could remove this.
+                                    let spanned_bounds = bounds
+                                        .to_token_stream()
+                                        .into_iter()
+                                        .map(|mut token| {
+                                            // NOTE: This is the only way we can
+                                            // impose a new span for a `bound`..
+                                            // FIXES: tests/ui/placeholder_with_bound.rs
+                                            // FIXES: tests/ui/trait-bound-not-satisfied.rs
+                                            token.set_span(ty.span());
+                                            token
+                                        })
+                                        .collect::<TokenStream2>();
+
+                                    let predicate: syn::WherePredicate =
+                                        parse_quote! {#ty: #spanned_bounds};
+
+                                    let predicates =
+                                        &mut self.subject.generics.make_where_clause().predicates;
+
+                                    // Wrapped fields can share the same inner type,
+                                    // e.g. `Box<Expr>` and `Rc<Expr>`.
+                                    if !predicates
+                                        .iter()
+                                        .any(|pred| pred.get_string() == predicate.get_string())
+                                    {
+                                        predicates.push(predicate)
+                                    }
+                                }
                             }
                         }
                     }
@@ -510,8 +794,8 @@ mod tests {
     use syn::{parse_quote, ItemTrait};
 
     use crate::{
-        dispatch::T_SHM,
-        factory::{PenumExpr, Subject},
+        dispatch,
+        factory::{with_crates, PenumExpr, Subject},
         penum::{Penum, Stringify},
     };
 
@@ -530,7 +814,7 @@ mod tests {
     fn register_trait(input: TokenStream) {
         let item_trait: ItemTrait = parse_quote!(#input);
         // If we cannot find the trait the user wants to dispatch, we need to store it.
-        T_SHM.insert(item_trait.ident.get_string(), item_trait.get_string());
+        dispatch::register_trait(None, &item_trait);
     }
 
     #[test]
@@ -622,7 +906,7 @@ mod tests {
                 V2(String)
             }
 
-            impl Abc<Input = str> for Enum {
+            impl Abc for Enum {
                 type Input = str;
                 fn get(&self) -> &Self::Input {
                     match self {
@@ -665,7 +949,7 @@ mod tests {
                 V2(i32, String)
             }
 
-            impl Abc<Input = str> for Enum {
+            impl Abc for Enum {
                 type Input = str;
                 fn get(&self) -> &Self::Input {
                     match self {
@@ -681,6 +965,791 @@ mod tests {
         penum_assertion(attr, input, expect);
     }
 
+    #[test]
+    #[rustfmt::skip]
+    fn dispatch_custom_trait_for_multiple_types() {
+        let blueprint = quote::quote!(
+            trait Echo {
+                fn echo(&self) -> String;
+            }
+        );
+
+        let attr = quote::quote!(
+            impl Echo for { Al, Be }
+        );
+
+        let input = quote::quote!(
+            enum Enum {
+                V1(Al),
+                V2(Be),
+                V3(Ce)
+            }
+        );
+
+        let expect = quote::quote!(
+            enum Enum where Al: Echo, Be: Echo {
+                V1(Al),
+                V2(Be),
+                V3(Ce)
+            }
+
+            impl Echo for Enum {
+                fn echo(&self) -> String {
+                    match self {
+                        Enum::V1(val) => val.echo(),
+                        Enum::V2(val) => val.echo(),
+                        _ => "".to_string()
+                    }
+                }
+            }
+        );
+
+        register_trait(blueprint);
+        penum_assertion(attr, input, expect);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn dispatch_custom_trait_registered_after_enum() {
+        let attr: PenumExpr = parse_quote!( (T) where T: ^Later );
+        let input: Subject = parse_quote!(
+            enum Enum {
+                V1(String),
+            }
+        );
+
+        let penum = Penum::new(attr, input).assemble();
+        let pending = penum.get_pending_dispatch("".to_string()).expect("to be pending");
+
+        assert!(pending.waiting.contains_key("Later"));
+        assert!(pending.dispatched.is_empty());
+
+        register_trait(quote::quote!(
+            trait Later {
+                fn later(&self) -> bool;
+            }
+        ));
+
+        let attr: PenumExpr = parse_quote!( (T) where T: ^Later );
+        let input: Subject = parse_quote!(
+            enum Enum {
+                V1(String),
+            }
+        );
+
+        let expect = quote::quote!(
+            impl Later for Enum {
+                fn later(&self) -> bool {
+                    match self {
+                        Enum::V1(val) => val.later(),
+                        _ => false
+                    }
+                }
+            }
+        );
+
+        let penum = Penum::new(attr, input)
+            .with_dispatched(pending.dispatched)
+            .assemble();
+
+        assert!(penum.get_pending_dispatch("".to_string()).is_none());
+        assert_eq!(penum.unwrap_impls_or_error().to_string(), expect.to_string());
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn dispatch_foreign_trait() {
+        let attr = quote::quote!( (T) where T: ^other::module::Foreign + ^AsRef<str> );
+        let input = quote::quote!(
+            enum Enum {
+                V1(String),
+            }
+        );
+
+        let expect = quote::quote!(
+            other::module::__penum_Foreign! {
+                (::penum::__dispatch)
+                [other::module::Foreign]
+                [(T) where T: ^other::module::Foreign + ^AsRef<str>]
+                { enum Enum { V1(String), } }
+                ["AsRef < str >"]
+            }
+        );
+
+        let penum = Penum::new(parse_quote!(#attr), parse_quote!(#input)).assemble();
+
+        assert!(penum.get_pending_dispatch("".to_string()).is_none());
+        assert_eq!(
+            penum.get_foreign_imports(&attr, &input).to_string(),
+            expect.to_string()
+        );
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn dispatch_module_trait() {
+        let attr = quote::quote!( (T) where T: ^net::Handler + ^other::Foreign + ^::net::Remote );
+        let input = quote::quote!(
+            enum Enum {
+                V1(String),
+            }
+        );
+
+        // `net` isn't a dependency, so it's a module whose trait hasn't
+        // been tagged yet, unless the path starts with `::`.
+        let expect = quote::quote!(
+            other::__penum_Foreign! {
+                (::penum::__dispatch)
+                [other::Foreign]
+                [(T) where T: ^net::Handler + ^other::Foreign + ^::net::Remote]
+                { enum Enum { V1(String), } }
+                []
+            }
+            ::net::__penum_Remote! {
+                (::penum::__dispatch)
+                [::net::Remote]
+                [(T) where T: ^net::Handler + ^other::Foreign + ^::net::Remote]
+                { enum Enum { V1(String), } }
+                []
+            }
+        );
+
+        with_crates(&["other"], || {
+            let penum = Penum::new(parse_quote!(#attr), parse_quote!(#input)).assemble();
+            let pending = penum.get_pending_dispatch("".to_string()).unwrap();
+
+            assert_eq!(pending.waiting.get("Handler").unwrap(), "net :: Handler");
+            assert_eq!(pending.waiting.len(), 1);
+            assert_eq!(
+                penum.get_foreign_imports(&attr, &input).to_string(),
+                expect.to_string()
+            );
+        });
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn dispatch_inline_trait() {
+        let attr = quote::quote!(
+            impl serde_like::Encode { fn encode(&self, out: &mut Vec<u8>); } for u8
+        );
+
+        let input = quote::quote!(
+            enum Enum {
+                V1(u8),
+                V2(i32)
+            }
+        );
+
+        let expect = quote::quote!(
+            enum Enum where u8: serde_like::Encode {
+                V1(u8),
+                V2(i32)
+            }
+
+            impl serde_like::Encode for Enum {
+                fn encode(&self, arg0: &mut Vec<u8>) {
+                    match self {
+                        Enum::V1(val) => val.encode(arg0),
+                        _ => ()
+                    }
+                }
+            }
+        );
+
+        penum_assertion(attr, input, expect);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn dispatch_forward_provided_methods() {
+        let attr = quote::quote!(
+            impl Iterator(forward = [size_hint]) for Countdown
+        );
+
+        let input = quote::quote!(
+            enum Enum {
+                V1(Countdown)
+            }
+        );
+
+        let expect = quote::quote!(
+            enum Enum where Countdown: Iterator {
+                V1(Countdown)
+            }
+
+            impl Iterator for Enum {
+                type Item = <Countdown as Iterator>::Item;
+                fn next(&mut self) -> Option<Self::Item> {
+                    match self {
+                        Enum::V1(val) => val.next(),
+                        _ => None
+                    }
+                }
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    match self {
+                        Enum::V1(val) => val.size_hint(),
+                        _ => (0, None)
+                    }
+                }
+            }
+        );
+
+        penum_assertion(attr, input, expect);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn dispatch_shared_method_name() {
+        let attr = quote::quote!(
+            impl Display for Timeout
+        );
+
+        let input = quote::quote!(
+            enum Enum {
+                V1(Timeout)
+            }
+        );
+
+        let expect = quote::quote!(
+            enum Enum where Timeout: Display {
+                V1(Timeout)
+            }
+
+            impl Display for Enum {
+                fn fmt(&self, arg0: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    match self {
+                        Enum::V1(val) => Display::fmt(val, arg0),
+                        _ => panic!("Missing arm")
+                    }
+                }
+            }
+        );
+
+        penum_assertion(attr, input, expect);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn dispatch_self_return() {
+        let attr = quote::quote!(
+            (T, ..) | unit where T: ^Neg<Output = Self>
+        );
+
+        let input = quote::quote!(
+            enum Enum {
+                V1(i32, String),
+                V2
+            }
+        );
+
+        let expect = quote::quote!(
+            enum Enum where i32: Neg<Output = i32> {
+                V1(i32, String),
+                V2
+            }
+
+            impl Neg for Enum {
+                type Output = Self;
+                fn neg(self) -> Self::Output {
+                    match self {
+                        Enum::V1(val, field1) => Enum::V1(val.neg(), field1),
+                        Self::V2 => Self::V2,
+                        _ => panic!("Missing arm")
+                    }
+                }
+            }
+        );
+
+        penum_assertion(attr, input, expect);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn dispatch_self_pairs() {
+        let attr = quote::quote!(
+            (T) | unit where T: ^PartialOrd(mismatch = order)
+        );
+
+        let input = quote::quote!(
+            enum Enum {
+                V1(i32),
+                V2
+            }
+        );
+
+        let expect = quote::quote!(
+            enum Enum where i32: PartialOrd {
+                V1(i32),
+                V2
+            }
+
+            impl PartialEq<Self> for Enum {
+                fn eq(&self, arg0: &Self) -> bool {
+                    match (self, arg0) {
+                        (Enum::V1(val), Enum::V1(arg0, ..)) => PartialEq::<_>::eq(val, arg0),
+                        (Self::V2, Self::V2) => {
+                            let ordering = ::core::cmp::Ordering::Equal;
+                            ordering.is_eq()
+                        },
+                        _ => {
+                            let order = |value: &Self| match value {
+                                Self::V1 { .. } => 0usize,
+                                Self::V2 { .. } => 1usize,
+                            };
+                            let ordering = order(self).cmp(&order(arg0));
+                            ordering.is_eq()
+                        }
+                    }
+                }
+            }
+
+            impl PartialOrd for Enum {
+                fn partial_cmp(&self, arg0: &Self) -> Option<::core::cmp::Ordering> {
+                    match (self, arg0) {
+                        (Enum::V1(val), Enum::V1(arg0, ..)) => PartialOrd::partial_cmp(val, arg0),
+                        (Self::V2, Self::V2) => {
+                            let ordering = ::core::cmp::Ordering::Equal;
+                            Some(ordering)
+                        },
+                        _ => {
+                            let order = |value: &Self| match value {
+                                Self::V1 { .. } => 0usize,
+                                Self::V2 { .. } => 1usize,
+                            };
+                            let ordering = order(self).cmp(&order(arg0));
+                            Some(ordering)
+                        }
+                    }
+                }
+            }
+        );
+
+        penum_assertion(attr, input, expect);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn dispatch_unsafe_trait() {
+        let blueprint = quote::quote!(
+            unsafe trait Raw {
+                unsafe fn read(&self, offset: usize) -> u8;
+            }
+        );
+
+        let attr = quote::quote!(
+            (T) where T: ^Raw
+        );
+
+        let input = quote::quote!(
+            enum Enum {
+                V1(Buffer)
+            }
+        );
+
+        let expect = quote::quote!(
+            enum Enum where Buffer: Raw {
+                V1(Buffer)
+            }
+
+            unsafe impl Raw for Enum {
+                unsafe fn read(&self, arg0: usize) -> u8 {
+                    match self {
+                        Enum::V1(val) => unsafe { val.read(arg0) },
+                        _ => 0
+                    }
+                }
+            }
+        );
+
+        register_trait(blueprint);
+        penum_assertion(attr, input, expect);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn dispatch_associated_const() {
+        let blueprint = quote::quote!(
+            trait Tag {
+                const ID: u32;
+            }
+        );
+
+        let attr = quote::quote!(
+            (T) | unit where T: ^Tag
+        );
+
+        let input = quote::quote!(
+            pub enum Enum {
+                V1(Alpha),
+                V2
+            }
+        );
+
+        let expect = quote::quote!(
+            pub enum Enum where Alpha: Tag {
+                V1(Alpha),
+                V2
+            }
+
+            impl Enum {
+                pub const fn id(&self) -> u32 {
+                    match self {
+                        Self::V1 { .. } => <Alpha as Tag>::ID,
+                        _ => 0
+                    }
+                }
+            }
+        );
+
+        register_trait(blueprint);
+        penum_assertion(attr, input, expect);
+    }
+
+    #[rustfmt::skip]
+    #[test]
+    fn dispatch_sum_type() {
+        let blueprint = quote::quote!(
+            trait Pick {
+                type Out;
+                fn pick(&self) -> Option<Self::Out>;
+            }
+        );
+
+        let attr = quote::quote!(
+            (T) where T: ^Pick(sum = [Out])
+        );
+
+        let input = quote::quote!(
+            enum Enum {
+                V1(Alpha),
+                V2(Beta),
+                V3(Alpha)
+            }
+        );
+
+        let expect = quote::quote!(
+            enum Enum where Beta: Pick, Alpha: Pick {
+                V1(Alpha),
+                V2(Beta),
+                V3(Alpha)
+            }
+
+            enum EnumOut {
+                V1(<Alpha as Pick>::Out),
+                V2(<Beta as Pick>::Out)
+            }
+
+            impl Pick for Enum {
+                type Out = EnumOut;
+                fn pick(&self) -> Option<Self::Out> {
+                    match self {
+                        Enum::V1(val) => (val.pick()).map(EnumOut::V1),
+                        Enum::V2(val) => (val.pick()).map(EnumOut::V2),
+                        Enum::V3(val) => (val.pick()).map(EnumOut::V1),
+                        _ => None
+                    }
+                }
+            }
+        );
+
+        register_trait(blueprint);
+        penum_assertion(attr, input, expect);
+    }
+
+    #[rustfmt::skip]
+    #[test]
+    fn dispatch_either_type() {
+        let attr = quote::quote!(
+            (T) | unit where T: ^IntoIterator<Item = u32>
+        );
+
+        let input = quote::quote!(
+            enum Enum {
+                V1(Vec<u32>),
+                V2
+            }
+        );
+
+        let expect = quote::quote!(
+            enum Enum where Vec<u32>: IntoIterator<Item = u32> {
+                V1(Vec<u32>),
+                V2
+            }
+
+            #[doc(hidden)]
+            enum __EnumIntoIter where <Vec<u32> as IntoIterator>::IntoIter: Iterator<Item = u32> {
+                V1(<Vec<u32> as IntoIterator>::IntoIter),
+                __Empty
+            }
+
+            impl Iterator for __EnumIntoIter {
+                type Item = u32;
+                fn next(&mut self) -> Option<Self::Item> {
+                    match self {
+                        __EnumIntoIter::V1(val) => val.next(),
+                        _ => None
+                    }
+                }
+            }
+
+            impl IntoIterator for Enum {
+                type Item = u32;
+                type IntoIter = __EnumIntoIter;
+                fn into_iter(self) -> Self::IntoIter {
+                    match self {
+                        Enum::V1(val) => __EnumIntoIter::V1(val.into_iter()),
+                        _ => __EnumIntoIter::__Empty
+                    }
+                }
+            }
+        );
+
+        penum_assertion(attr, input, expect);
+    }
+
+    #[rustfmt::skip]
+    #[test]
+    fn dispatch_pinned_future() {
+        let attr = quote::quote!(
+            (T) where T: ^Future<Output = u32>
+        );
+
+        let input = quote::quote!(
+            enum Enum {
+                V1(Alpha)
+            }
+        );
+
+        let expect = quote::quote!(
+            enum Enum where Alpha: Future<Output = u32> {
+                V1(Alpha)
+            }
+
+            impl Future for Enum {
+                type Output = u32;
+                fn poll(self: ::core::pin::Pin<&mut Self>, arg0: &mut ::core::task::Context<'_>) -> ::core::task::Poll<Self::Output> {
+                    match unsafe { ::core::pin::Pin::get_unchecked_mut(self) } {
+                        Enum::V1(val) => (unsafe { ::core::pin::Pin::new_unchecked(val) }).poll(arg0),
+                        _ => panic!("Missing arm")
+                    }
+                }
+            }
+
+            const _: () = {
+                #[doc(hidden)]
+                #[allow(dead_code)]
+                pub struct __Pinned<'__pin> {
+                    __pin: ::core::marker::PhantomData<&'__pin ()>,
+                    __enum: ::core::marker::PhantomData<fn() -> Enum>,
+                    __field0: Alpha
+                }
+
+                impl<'__pin> ::core::marker::Unpin for Enum where __Pinned<'__pin>: ::core::marker::Unpin {}
+
+                trait MustNotImplDrop {}
+
+                #[allow(drop_bounds)]
+                impl<T: ::core::ops::Drop> MustNotImplDrop for T {}
+
+                impl MustNotImplDrop for Enum {}
+            };
+        );
+
+        penum_assertion(attr, input, expect);
+    }
+
+    #[rustfmt::skip]
+    #[test]
+    fn dispatch_callable() {
+        let attr = quote::quote!(
+            (T) | unit where T: ^Fn(i32) -> bool
+        );
+
+        let input = quote::quote!(
+            pub enum Enum {
+                V1(fn(i32) -> bool),
+                V2
+            }
+        );
+
+        let expect = quote::quote!(
+            pub enum Enum where fn(i32) -> bool: Fn(i32) -> bool {
+                V1(fn(i32) -> bool),
+                V2
+            }
+
+            impl Enum {
+                pub fn call(&self, arg0: i32) -> bool {
+                    match self {
+                        Enum::V1(val) => val(arg0),
+                        _ => false
+                    }
+                }
+                pub fn call_mut(&mut self, arg0: i32) -> bool {
+                    match self {
+                        Enum::V1(val) => val(arg0),
+                        _ => false
+                    }
+                }
+                pub fn call_once(self, arg0: i32) -> bool {
+                    match self {
+                        Enum::V1(val) => ({ val })(arg0),
+                        _ => false
+                    }
+                }
+
+                pub fn to_fn(&self) -> impl Fn(i32) -> bool + '_ {
+                    move |arg0| self.call(arg0)
+                }
+            }
+        );
+
+        penum_assertion(attr, input, expect);
+    }
+
+    #[rustfmt::skip]
+    #[test]
+    fn dispatch_through_wrappers() {
+        let blueprint = quote::quote!(
+            trait Eval {
+                fn eval(&self) -> i64;
+            }
+        );
+
+        let attr = quote::quote!(
+            (T) where T: ^Eval(deref = all)
+        );
+
+        let input = quote::quote!(
+            pub enum Expr {
+                Neg(Box<Neg>),
+                Opt(Option<Rc<Lit>>)
+            }
+        );
+
+        let expect = quote::quote!(
+            pub enum Expr where Lit: Eval, Neg: Eval {
+                Neg(Box<Neg>),
+                Opt(Option<Rc<Lit> >)
+            }
+
+            impl Eval for Expr {
+                fn eval(&self) -> i64 {
+                    match self {
+                        Expr::Neg(val) => {
+                            let val = &**val;
+                            val.eval()
+                        },
+                        Expr::Opt(val) => match val {
+                            ::core::option::Option::Some(val) => {
+                                let val = &**val;
+                                val.eval()
+                            },
+                            ::core::option::Option::None => 0,
+                        },
+                        _ => 0
+                    }
+                }
+            }
+        );
+
+        register_trait(blueprint);
+        penum_assertion(attr, input, expect);
+    }
+
+    #[rustfmt::skip]
+    #[test]
+    fn dispatch_strict() {
+        let blueprint = quote::quote!(
+            trait Name {
+                fn name(&self) -> &str;
+            }
+        );
+
+        let attr = quote::quote!(
+            strict, (T) where T: ^Name
+        );
+
+        let input = quote::quote!(
+            pub enum Enum {
+                V1(Alpha),
+                V2(Beta)
+            }
+        );
+
+        let expect = quote::quote!(
+            pub enum Enum where Beta: Name, Alpha: Name {
+                V1(Alpha),
+                V2(Beta)
+            }
+
+            impl Name for Enum {
+                fn name(&self) -> &str {
+                    match self {
+                        Enum::V1(val) => val.name(),
+                        Enum::V2(val) => val.name(),
+                    }
+                }
+            }
+        );
+
+        register_trait(blueprint);
+        penum_assertion(attr, input, expect);
+    }
+
+    #[rustfmt::skip]
+    #[test]
+    fn dispatch_arm_override() {
+        let blueprint = quote::quote!(
+            trait Label {
+                fn label(&self, prefix: &str) -> String;
+            }
+        );
+
+        let attr = quote::quote!(
+            (T) | unit where T: ^Label(fallback(label = prefix.to_string()))
+        );
+
+        let input = quote::quote!(
+            pub enum Enum {
+                V1(Alpha),
+                #[penum(arm(Label::label = format!("{prefix}v2")))]
+                V2(Beta),
+                V3
+            }
+        );
+
+        let expect = quote::quote!(
+            pub enum Enum where Beta: Label, Alpha: Label {
+                V1(Alpha),
+                V2(Beta),
+                V3
+            }
+
+            impl Label for Enum {
+                fn label(&self, arg0: &str) -> String {
+                    match self {
+                        Enum::V1(val) => val.label(arg0),
+                        Self::V2 { .. } => {
+                            #[allow(unused_variables, unused_mut)]
+                            let (prefix,) = (arg0,);
+                            format!("{prefix}v2")
+                        },
+                        _ => {
+                            #[allow(unused_variables, unused_mut)]
+                            let (prefix,) = (arg0,);
+                            prefix.to_string()
+                        }
+                    }
+                }
+            }
+        );
+
+        register_trait(blueprint);
+        penum_assertion(attr, input, expect);
+    }
+
     // TODO: Decide how variadics should be interpreted when we have concrete type bounds.
     // Make sure to update `tests/test-concrete-bound.rs` if this later gets supported.
 }
@@ -1,37 +1,249 @@
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
 use quote::format_ident;
 use quote::ToTokens;
+use syn::parse;
+use syn::parse2;
 use syn::parse_macro_input;
+use syn::parse_quote;
+use syn::parse_str;
+use syn::Ident;
+use syn::ItemConst;
 use syn::ItemTrait;
+use syn::Path;
 use syn::Type;
-
-use crate::dispatch::T_SHM;
+use syn::TypeParamBound;
+
+use crate::dispatch::export_trait;
+use crate::dispatch::qualify_supertraits;
+use crate::dispatch::register_fallback;
+use crate::dispatch::register_trait;
+use crate::dispatch::register_trait_as;
+use crate::dispatch::Blueprint;
+use crate::dispatch::ForeignDispatch;
+use crate::dispatch::TraitAttr;
+use crate::dispatch::D_SHM;
 use crate::factory::PenumExpr;
 use crate::factory::Subject;
 use crate::penum::Penum;
+use crate::utils::fallback_overwritten;
+use crate::utils::trait_definition_changed;
 use crate::utils::Stringify;
 
 pub fn penum_expand(attr: TokenStream, input: TokenStream) -> TokenStream {
-    // TODO: Make it bi-directional, meaning it's also possible to register enums and then do
-    // the implementations when we tag a trait. (That is actually better).
-    if attr.is_empty() {
-        let output = input.clone();
-        let item_trait = parse_macro_input!(input as ItemTrait);
+    if let Ok(item_trait) = parse::<ItemTrait>(input.clone()) {
+        let TraitAttr { path } = parse_macro_input!(attr as TraitAttr);
+        let trait_name = item_trait.ident.get_string();
+
+        // Enums in other crates can dispatch the trait through this export.
+        let mut output = TokenStream2::from(input);
 
         // If we cannot find the trait the user wants to dispatch, we need to store it.
-        T_SHM.insert(item_trait.ident.get_string(), item_trait.get_string());
+        if !register_trait(path.as_ref(), &item_trait) {
+            let message = trait_definition_changed(&item_trait.ident);
+            output.extend(syn::Error::new_spanned(&item_trait.ident, message).to_compile_error());
+        }
 
-        output
+        output.extend(export_trait(&item_trait));
+
+        // Enums that were expanded before this trait are waiting for us to
+        // implement it.
+        output.extend(resume_pending_dispatch(path.as_ref(), &trait_name));
+        output.into()
     } else {
+        let expr_string = attr.to_string();
+        let subject_string = input.to_string();
+        let expr_tokens = TokenStream2::from(attr.clone());
+        let subject_tokens = TokenStream2::from(input.clone());
+
         let expr = parse_macro_input!(attr as PenumExpr);
         let subject = parse_macro_input!(input as Subject);
 
-        let penum = Penum::new(expr, subject);
+        let penum = Penum::new(expr, subject).assemble();
+
+        // Dispatched traits that cannot be found yet are implemented once
+        // they get tagged with `#[penum]`.
+        if let Some(pending) = penum.get_pending_dispatch(expr_string) {
+            D_SHM.insert(subject_string, pending);
+        }
+
+        // Traits from other crates are implemented once their exported
+        // macro calls us back.
+        let imports = penum.get_foreign_imports(&expr_tokens, &subject_tokens);
 
         // Loop through enum definition and match each variant with each
         // shape pattern. for each variant => pattern.find(variant)
-        penum.assemble().unwrap_or_error()
+        let mut output = TokenStream2::from(penum.unwrap_or_error());
+        output.extend(imports);
+        output.into()
+    }
+}
+
+/// Called by the macro that another crate exported together with its
+/// trait, see `export_trait`. Only the impls of that trait are emitted,
+/// as the enum itself has already been emitted.
+pub fn dispatch_expand(input: TokenStream) -> TokenStream {
+    let ForeignDispatch {
+        ty,
+        expr,
+        subject,
+        mut dispatched,
+        schematic,
+        supertraits,
+    } = parse_macro_input!(input as ForeignDispatch);
+
+    let Some(krate) = get_first_segment(&ty) else {
+        return TokenStream::new();
+    };
+
+    // Supertraits are registered under the path that the trait refers to
+    // them with, e.g. `other_crate::inner::Speak`.
+    let mut schematics = std::iter::once(schematic)
+        .chain(supertraits)
+        .collect::<Vec<_>>();
+    schematics
+        .iter_mut()
+        .for_each(|schematic| qualify_supertraits(schematic, krate));
+
+    let paths = schematics
+        .iter()
+        .flat_map(|schematic| schematic.supertraits.iter())
+        .filter_map(|bound| match bound {
+            TypeParamBound::Trait(tb) => Some(tb.path.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    for (i, schematic) in schematics.iter().enumerate() {
+        let ident = &schematic.ident;
+        let path = match &ty {
+            Type::Path(path) if i == 0 => path.path.clone(),
+            _ => paths
+                .iter()
+                .find(|path| path.segments.last().is_some_and(|s| s.ident.eq(ident)))
+                .cloned()
+                .unwrap_or_else(|| parse_quote!(#krate::#ident)),
+        };
+
+        register_trait_as(&path, schematic);
+    }
+
+    let (mut expr, subject) = match (parse2::<PenumExpr>(expr), parse2::<Subject>(subject)) {
+        (Ok(expr), Ok(subject)) => (expr, subject),
+        (Err(err), _) | (_, Err(err)) => return err.to_compile_error().into(),
+    };
+
+    // Every other dispatched trait is implemented elsewhere, so make sure
+    // that we don't implement them as supertraits of this one.
+    let ty = ty.get_string();
+    expr.retain_dispatch(|bound| {
+        let keep = bound.ty.get_string() == ty;
+        if !keep {
+            dispatched.insert(Blueprint::bound_id(bound).get_string());
+        }
+        keep
+    });
+
+    Penum::new(expr, subject)
+        .with_dispatched(dispatched)
+        .assemble()
+        .unwrap_impls_or_error()
+        .into()
+}
+
+fn get_first_segment(ty: &Type) -> Option<&Ident> {
+    match ty {
+        Type::Path(path) => path.path.segments.first().map(|segment| &segment.ident),
+        _ => None,
+    }
+}
+
+/// Re-assemble every enum that is waiting on `trait_name`, and only emit
+/// the impls that haven't been emitted before. The impls end up next to
+/// the trait, so they are wrapped in the scope of the enum if it's in
+/// another module, see `get_enum_scope`.
+fn resume_pending_dispatch(module: Option<&Path>, trait_name: &str) -> TokenStream2 {
+    let mut output = TokenStream2::new();
+
+    for (subject_string, pending) in D_SHM.take_where(|p| p.waiting.contains_key(trait_name)) {
+        let (Ok(expr), Ok(subject), Ok(bound)) = (
+            parse_str::<PenumExpr>(&pending.expr),
+            parse_str::<Subject>(&subject_string),
+            parse_str::<Path>(&pending.waiting[trait_name]),
+        ) else {
+            continue;
+        };
+
+        let penum = Penum::new(expr, subject)
+            .with_dispatched(pending.dispatched)
+            .assemble();
+
+        // The enum might still be waiting on other traits.
+        if let Some(pending) = penum.get_pending_dispatch(pending.expr) {
+            D_SHM.insert(subject_string, pending);
+        }
+
+        let impls = penum.unwrap_impls_or_error();
+
+        match get_enum_scope(module, &bound) {
+            Some(scope) => output.extend(quote::quote!(
+                const _: () = {
+                    use #scope::*;
+                    #impls
+                };
+            )),
+            None => output.extend(impls),
+        }
+    }
+
+    output
+}
+
+/// The module of an enum that dispatches the trait in `module` with
+/// `bound`, if it's another module. The path of the bound is relative to
+/// the enum, so the enum is in what's left of the module.
+///
+/// ```text
+/// #[penum(path = app::net)] trait Handler
+///
+/// ^net::Handler   =>  crate::app
+/// ^Handler        =>  None, the enum is next to the trait
+/// ```
+fn get_enum_scope(module: Option<&Path>, bound: &Path) -> Option<Path> {
+    let module = module?
+        .segments
+        .iter()
+        .map(|segment| &segment.ident)
+        .skip_while(|ident| *ident == "crate")
+        .collect::<Vec<_>>();
+
+    let mut segments = bound
+        .segments
+        .iter()
+        .map(|segment| &segment.ident)
+        .peekable();
+
+    // Absolute paths don't tell where the enum is.
+    if bound.leading_colon.is_some()
+        || segments
+            .peek()
+            .is_some_and(|ident| *ident == "crate" || *ident == "super")
+    {
+        return None;
+    }
+
+    let bound = segments
+        .skip_while(|ident| *ident == "self")
+        .collect::<Vec<_>>();
+    let bound_module = &bound[..bound.len().saturating_sub(1)];
+
+    if bound_module.is_empty() || !module.ends_with(bound_module) {
+        return None;
     }
+
+    let scope = &module[..module.len() - bound_module.len()];
+
+    Some(parse_quote!(crate #(::#scope)*))
 }
 
 pub fn to_string_expand(input: TokenStream) -> TokenStream {
@@ -156,6 +368,28 @@ pub fn static_str(input: TokenStream) -> TokenStream {
     )
 }
 
+pub fn fallback_expand(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let TraitAttr { path } = parse_macro_input!(attr as TraitAttr);
+    let item_const = parse_macro_input!(input as ItemConst);
+
+    let mut output = item_const.to_token_stream();
+
+    // There is no stable way for us to emit a warning, so we let rustc
+    // warn about a deprecated item instead.
+    if let Some(previous) = register_fallback(path.as_ref(), &item_const) {
+        let note = fallback_overwritten(&item_const.ty, &previous, &item_const.ident);
+        let warning = quote::quote_spanned!(item_const.ident.span()=>
+            #[deprecated(note = #note)]
+            struct FallbackOverwritten;
+            let _ = FallbackOverwritten;
+        );
+
+        output.extend(quote::quote!(const _: () = { #warning };));
+    }
+
+    output.into()
+}
+
 /// UNDER DEVELOPMENT
 pub fn lazy_string(input: TokenStream) -> TokenStream {
     let subject = parse_macro_input!(input as Subject);
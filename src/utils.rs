@@ -16,7 +16,8 @@ use syn::{
     punctuated::Punctuated,
     spanned::Spanned,
     token::{self, Add},
-    Expr, Fields, Token, TraitBound, Type, TypeImplTrait, TypeParamBound, Variant, WhereClause,
+    Attribute, Expr, Fields, Token, TraitBound, Type, TypeImplTrait, TypeParamBound, Variant,
+    WhereClause,
 };
 
 use crate::{
@@ -27,6 +28,10 @@ use crate::{
 
 pub const DEFAULT_VARIANT_SYMBOL: &str = "default";
 pub const ABSTRACT_MACRO_EXPR_SYMBOL: &str = "implement";
+pub const HELPER_ATTR_SYMBOL: &str = "penum";
+pub const DISPATCH_ATTR_SYMBOL: &str = "dispatch";
+pub const ARM_ATTR_SYMBOL: &str = "arm";
+pub const EXPORT_MACRO_PREFIX: &str = "__penum_";
 
 pub fn no_match_found(item: &impl ToTokens, pat: &str) -> String {
     format!(
@@ -47,6 +52,173 @@ pub fn lifetime_not_permitted() -> &'static str {
     "Lifetime annotation not permitted"
 }
 
+pub fn ambiguous_dispatch(variant: &Ident, bound: &impl ToTokens) -> String {
+    format!(
+        "`{}` has more than one field that can be dispatched with `{}`. Mark the one that should be dispatched with `^` in the pattern, or with `#[penum(dispatch)]` on the field.",
+        variant,
+        bound.get_string()
+    )
+}
+
+pub fn supertrait_not_found(supertrait: &Ident, subtrait: &Ident) -> String {
+    format!(
+        "`{}` is a supertrait of `{}` and cannot be dispatched. Make sure the trait is tagged with the `#[penum]` attribute before `{}` is.",
+        supertrait, subtrait, subtrait
+    )
+}
+
+pub fn trait_not_found(name: &Ident) -> String {
+    format!(
+        "`{}` cannot be found. Make sure the trait is tagged with the `#[penum]` attribute.",
+        name
+    )
+}
+
+pub fn generated_bound_not_dispatched(bound: &Ident, source: &Ident, name: &Ident) -> String {
+    format!(
+        "`{}` cannot be dispatched on the type generated for `{}` of `{}`. Make sure the trait is tagged with the `#[penum]` attribute before your enum.",
+        bound, source, name
+    )
+}
+
+pub fn ambiguous_trait(name: &Ident, candidates: &[String]) -> String {
+    format!(
+        "`{}` is ambiguous, it could be any of `{}`. Use a path to choose one, e.g. `^{}`.",
+        name,
+        candidates.join("`, `"),
+        candidates.first().map(String::as_str).unwrap_or_default()
+    )
+}
+
+pub fn unqualified_trait(name: &Ident) -> String {
+    format!(
+        "`{}` is tagged with `#[penum]` in more than one module. Tag each of them with `#[penum(path = module)]`, and use the path to choose one, e.g. `^module::{0}`.",
+        name
+    )
+}
+
+pub fn trait_definition_changed(name: &Ident) -> String {
+    format!(
+        "`{}` has changed since the last build, and has already been dispatched as a supertrait with its previous definition. Build again to dispatch the new definition, or tag the trait with `#[penum]` before the traits that depend on it.",
+        name
+    )
+}
+
+pub fn unknown_forward(method: &Ident, name: &Ident) -> String {
+    format!(
+        "`{}` is not a method of `{}` and cannot be forwarded.",
+        method, name
+    )
+}
+
+pub fn unknown_sum_type(assoc: &Ident, name: &Ident) -> String {
+    format!(
+        "`{}` is not an associated type of `{}` and cannot be unified into a sum type.",
+        assoc, name
+    )
+}
+
+pub fn unsupported_sum_type(assoc: &Ident, method: &Ident) -> String {
+    format!(
+        "`{}` is used by `{}` in a way that cannot be unified into a sum type. Only `{0}`, `Option<{0}>` and `Result<{0}, _>` return types are supported.",
+        assoc, method
+    )
+}
+
+pub fn pinned_deref_not_permitted(method: &Ident, name: &Ident) -> String {
+    format!(
+        "`{}` cannot be dispatched with `deref`, as `{}` takes `self: Pin<&mut Self>` and the pin cannot be projected through a wrapper. Remove `deref`, or dispatch a field that holds the value inline.",
+        name, method
+    )
+}
+
+pub fn missing_dispatch_arms(name: &Ident, missing: &[(Ident, Vec<Ident>)]) -> String {
+    let missing = missing
+        .iter()
+        .map(|(method, variants)| {
+            let variants = variants
+                .iter()
+                .map(|v| format!("`{v}`"))
+                .collect::<Vec<_>>();
+            format!("`{}` for {}", method, variants.join(", "))
+        })
+        .collect::<Vec<_>>();
+
+    format!(
+        "`{}` has no fallback for variants that cannot be dispatched, or that dispatch through an `Option` which can be `None`, which is not allowed in strict mode: {}. Add a dispatchable field to each variant, a `fallback(..)` for the method, or remove `strict`.",
+        name,
+        missing.join("; ")
+    )
+}
+
+pub fn fallback_overwritten(ty: &Type, previous: &str, ident: &Ident) -> String {
+    format!(
+        "`{}` replaces `{}` as the fallback of `{}`. Use `#[penum::fallback(path = module)]` to register the fallbacks of types with the same name in different modules.",
+        ident,
+        previous.replace(' ', ""),
+        ty.get_string()
+    )
+}
+
+pub fn unknown_fallback(method: &Ident, name: &Ident) -> String {
+    format!(
+        "`{}` is not a method of `{}` and cannot have a fallback.",
+        method, name
+    )
+}
+
+pub fn unknown_arm_trait(name: &impl ToTokens, variant: &Ident) -> String {
+    format!(
+        "`{}` is not dispatched for this enum, so `{}` cannot override one of its methods.",
+        name.get_string(),
+        variant
+    )
+}
+
+pub fn unknown_arm_method(method: &Ident, name: &Ident) -> String {
+    format!(
+        "`{}` is not a method of `{}` and cannot be overridden.",
+        method, name
+    )
+}
+
+pub fn conflicting_dispatch_markers(variant: &Ident, bound: &impl ToTokens) -> String {
+    format!(
+        "`{}` has more than one field marked for dispatch with `{}`.",
+        variant,
+        bound.get_string()
+    )
+}
+
+/// Used to check if an attribute is a penum helper attribute, e.g.
+/// `#[penum(dispatch)]`.
+pub fn is_helper_attr(attr: &Attribute) -> bool {
+    attr.path.is_ident(HELPER_ATTR_SYMBOL)
+}
+
+/// Used to check if a field has been marked with `#[penum(dispatch)]`.
+pub fn is_dispatch_attr(attr: &Attribute) -> bool {
+    is_helper_attr(attr)
+        && attr
+            .parse_args::<Ident>()
+            .map(|ident| ident == DISPATCH_ATTR_SYMBOL)
+            .unwrap_or_default()
+}
+
+/// Used to check if a variant has a hand-written arm, e.g.
+/// `#[penum(arm(Display::fmt = write!(f, "none")))]`.
+pub fn is_arm_attr(attr: &Attribute) -> bool {
+    is_helper_attr(attr)
+        && attr
+            .parse_args_with(|input: ParseStream| {
+                let ident: Ident = input.parse()?;
+                input.parse::<proc_macro2::TokenStream>()?;
+                Ok(ident)
+            })
+            .map(|ident| ident == ARM_ATTR_SYMBOL)
+            .unwrap_or_default()
+}
+
 pub fn create_unique_ident(value: &str, tag: &Ident, span: Span) -> Ident {
     format_ident!("_{}_{}", tag, value, span = span)
 }
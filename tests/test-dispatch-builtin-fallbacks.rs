@@ -0,0 +1,127 @@
+extern crate penum;
+use penum::penum;
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[penum]
+trait Shape {
+    fn area(&self) -> f64;
+    fn corners(&self) -> [(f32, f32); 4];
+    fn names(&self) -> [String; 2];
+    fn flags(&self) -> &[Option<String>; 2];
+    fn points(&self) -> &[u8];
+    fn points_mut(&mut self) -> &mut [u8];
+    fn tags(&self) -> Vec<String>;
+    fn index(&self) -> &HashMap<String, usize>;
+    fn label(&self) -> Cow<'_, str>;
+    fn label_ref(&self) -> &Cow<'static, [u8]>;
+    fn boxed(&self) -> Box<(bool, Option<u8>)>;
+    fn shared(&self) -> Rc<str>;
+    fn synced(&self) -> Arc<Vec<u8>>;
+    fn ttl(&self) -> &Duration;
+    fn marker(&self) -> PhantomData<u8>;
+    fn draw(&self) -> ();
+}
+
+struct Square(f64, Vec<u8>);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+
+    fn corners(&self) -> [(f32, f32); 4] {
+        [(0.0, 0.0); 4]
+    }
+
+    fn names(&self) -> [String; 2] {
+        ["square".to_string(), "quad".to_string()]
+    }
+
+    fn flags(&self) -> &[Option<String>; 2] {
+        &[None, None]
+    }
+
+    fn points(&self) -> &[u8] {
+        &self.1
+    }
+
+    fn points_mut(&mut self) -> &mut [u8] {
+        &mut self.1
+    }
+
+    fn tags(&self) -> Vec<String> {
+        vec!["square".to_string()]
+    }
+
+    fn index(&self) -> &HashMap<String, usize> {
+        unimplemented!()
+    }
+
+    fn label(&self) -> Cow<'_, str> {
+        Cow::Borrowed("square")
+    }
+
+    fn label_ref(&self) -> &Cow<'static, [u8]> {
+        unimplemented!()
+    }
+
+    fn boxed(&self) -> Box<(bool, Option<u8>)> {
+        Box::new((true, Some(4)))
+    }
+
+    fn shared(&self) -> Rc<str> {
+        Rc::from("square")
+    }
+
+    fn synced(&self) -> Arc<Vec<u8>> {
+        Arc::new(self.1.clone())
+    }
+
+    fn ttl(&self) -> &Duration {
+        unimplemented!()
+    }
+
+    fn marker(&self) -> PhantomData<u8> {
+        PhantomData
+    }
+
+    fn draw(&self) {}
+}
+
+// Every method has a built-in fallback, so this passes in strict mode.
+#[penum( strict, (T) | unit where T: ^Shape )]
+enum Canvas {
+    Square(Square),
+    Blank,
+}
+
+fn main() {
+    let square = Canvas::Square(Square(2.0, vec![1, 2]));
+    assert_eq!(square.area(), 4.0);
+    assert_eq!(square.points(), &[1, 2]);
+    assert_eq!(&*square.shared(), "square");
+
+    let mut blank = Canvas::Blank;
+    assert_eq!(blank.area(), 0.0);
+    assert_eq!(blank.corners(), [(0.0, 0.0); 4]);
+    assert_eq!(blank.names(), [String::new(), String::new()]);
+    assert_eq!(blank.flags(), &[None, None]);
+    assert!(blank.points().is_empty());
+    assert!(blank.points_mut().is_empty());
+    assert!(blank.tags().is_empty());
+    assert!(blank.index().is_empty());
+    assert_eq!(blank.label(), "");
+    assert!(blank.label_ref().is_empty());
+    assert_eq!(*blank.boxed(), (false, None));
+    assert_eq!(&*blank.shared(), "");
+    assert!(blank.synced().is_empty());
+    assert_eq!(blank.ttl(), &Duration::ZERO);
+    assert_eq!(blank.marker(), PhantomData);
+    assert_eq!(blank.draw(), ());
+}
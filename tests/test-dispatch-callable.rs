@@ -0,0 +1,92 @@
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+
+fn quit(_: &str) -> Result<String, String> {
+    Err("bye".to_string())
+}
+
+#[penum( (T) | { handler: T, .. } | unit where T: ^Fn(&str) -> Result<String, String> )]
+enum Command<F: Fn(&str) -> Result<String, String>> {
+    Echo(F),
+    Quit(fn(&str) -> Result<String, String>),
+    Boxed(Box<dyn Fn(&str) -> Result<String, String>>),
+    Named { handler: F, name: &'static str },
+    Noop,
+}
+
+fn trim(input: &str) -> &str {
+    input.trim()
+}
+
+// The returned `&str` borrows from the argument, not from the enum.
+#[penum( (T) | unit where T: ^Fn(&str) -> &str )]
+enum Transform<F: Fn(&str) -> &str> {
+    Closure(F),
+    Pointer(fn(&str) -> &str),
+    Identity,
+}
+
+#[penum( (T) where T: ^FnMut(u32) )]
+enum Counter<F: FnMut(u32)> {
+    Closure(F),
+    Pointer(fn(u32)),
+}
+
+#[penum( (T) where T: ^FnOnce() -> String )]
+enum Finish<F: FnOnce() -> String> {
+    Closure(F),
+}
+
+fn main() {
+    let echo = Command::Echo(|input: &str| Ok(input.to_uppercase()));
+    assert_eq!(echo.call("hi"), Ok("HI".to_string()));
+    assert_eq!(echo.call_once("once"), Ok("ONCE".to_string()));
+
+    let mut quit: Command<fn(&str) -> Result<String, String>> = Command::Quit(quit);
+    assert_eq!(quit.call_mut("now"), Err("bye".to_string()));
+
+    let boxed: Command<fn(&str) -> _> = Command::Boxed(Box::new(|input| Ok(input.repeat(2))));
+    let results = ["a", "b"]
+        .into_iter()
+        .map(boxed.to_fn())
+        .collect::<Vec<_>>();
+    assert_eq!(results, [Ok("aa".to_string()), Ok("bb".to_string())]);
+
+    let named = Command::Named {
+        handler: |input: &str| Ok(input.len().to_string()),
+        name: "len",
+    };
+    assert_eq!(named.call("four"), Ok("4".to_string()));
+
+    // Unit variants fall back to a default, which is `Err` for results.
+    let noop: Command<fn(&str) -> _> = Command::Noop;
+    assert_eq!(noop.call("ignored"), Err(String::new()));
+
+    let trimmed = {
+        let transform: Transform<fn(&str) -> &str> = Transform::Pointer(trim);
+        transform.call("  padded  ")
+    };
+    assert_eq!(trimmed, "padded");
+
+    let first = Transform::Closure(|input: &str| input.split(',').next().unwrap_or(input));
+    let words = ["a,b", "c"]
+        .into_iter()
+        .map(first.to_fn())
+        .collect::<Vec<_>>();
+    assert_eq!(words, ["a", "c"]);
+
+    let identity: Transform<fn(&str) -> &str> = Transform::Identity;
+    assert_eq!(identity.call("kept"), "");
+
+    let mut total = 0;
+    let mut counter = Counter::Closure(|n| total += n);
+    counter.call_mut(2);
+    (1..=3).for_each(counter.to_fn_mut());
+    drop(counter);
+    assert_eq!(total, 8);
+
+    let greeting = String::from("done");
+    let finish = Finish::Closure(move || greeting);
+    assert_eq!(finish.call_once(), "done");
+}
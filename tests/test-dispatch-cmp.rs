@@ -0,0 +1,138 @@
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+#[penum( (T) | unit where T: ^Clone + ^PartialEq )]
+#[derive(Debug)]
+enum Value {
+    Text(String),
+    Num(i64),
+    Empty,
+}
+
+#[penum( (T) | unit where T: ^PartialOrd )]
+#[derive(Debug)]
+enum Reading {
+    Celsius(f32),
+    Kelvin(f64),
+    Missing,
+}
+
+#[penum( (T) | unit where T: ^Ord(mismatch = order) )]
+#[derive(Debug)]
+enum Rank {
+    Score(u32),
+    Name(&'static str),
+    Unranked,
+}
+
+#[penum( { value: T, .. } | unit where T: ^PartialOrd + ^PartialEq )]
+#[derive(Debug)]
+enum Sample {
+    Exact { value: i32 },
+    Approx { value: f64, margin: f64 },
+    Missing,
+}
+
+#[penum( (T, ..) | { id: T, .. } | unit where T: ^Ord(mismatch = order) + ^PartialOrd )]
+#[derive(Debug)]
+enum Key {
+    Index(usize, &'static str),
+    Named { id: &'static str },
+    Root,
+}
+
+fn main() {
+    // Clone rebuilds the variant around the cloned field.
+    let text = Value::Text("a".to_string());
+    assert_eq!(text.clone(), text);
+    assert_eq!(Value::Num(3).clone(), Value::Num(3));
+    assert_eq!(Value::Empty.clone(), Value::Empty);
+
+    // Different variants are never equal, but unit variants are equal
+    // to themselves.
+    assert_eq!(Value::Num(1), Value::Num(1));
+    assert_ne!(Value::Num(1), Value::Num(2));
+    assert_ne!(Value::Num(1), Value::Text("1".to_string()));
+    assert_ne!(Value::Num(1), Value::Empty);
+    assert_eq!(Value::Empty, Value::Empty);
+
+    assert!(Reading::Celsius(1.0) < Reading::Celsius(2.0));
+    assert_eq!(
+        Reading::Kelvin(1.0).partial_cmp(&Reading::Kelvin(1.0)),
+        Some(Ordering::Equal)
+    );
+    assert_eq!(
+        Reading::Celsius(1.0).partial_cmp(&Reading::Kelvin(1.0)),
+        None
+    );
+    assert_eq!(
+        Reading::Missing.partial_cmp(&Reading::Missing),
+        Some(Ordering::Equal)
+    );
+    assert_eq!(Reading::Missing, Reading::Missing);
+    assert!(Reading::Missing <= Reading::Missing);
+
+    // Different variants are ordered by their position.
+    assert_eq!(Rank::Score(1).cmp(&Rank::Score(2)), Ordering::Less);
+    assert_eq!(Rank::Score(9).cmp(&Rank::Name("a")), Ordering::Less);
+    assert_eq!(Rank::Unranked.cmp(&Rank::Unranked), Ordering::Equal);
+    assert_eq!(Rank::Unranked.max(Rank::Score(1)), Rank::Unranked);
+
+    // Struct variants compare their dispatched field only.
+    assert_eq!(Sample::Exact { value: 1 }, Sample::Exact { value: 1 });
+    assert!(Sample::Exact { value: 1 } < Sample::Exact { value: 2 });
+    assert_eq!(
+        Sample::Approx {
+            value: 1.0,
+            margin: 0.1
+        },
+        Sample::Approx {
+            value: 1.0,
+            margin: 0.5
+        }
+    );
+    assert_eq!(
+        Sample::Exact { value: 1 }.partial_cmp(&Sample::Approx {
+            value: 1.0,
+            margin: 0.0
+        }),
+        None
+    );
+    assert_eq!(Sample::Missing, Sample::Missing);
+
+    // Tuple, struct and unit variants in the same enum.
+    assert_eq!(Key::Index(1, "a"), Key::Index(1, "b"));
+    assert!(Key::Index(1, "a") < Key::Index(2, "a"));
+    assert!(Key::Named { id: "a" } < Key::Named { id: "b" });
+    assert!(Key::Index(9, "z") < Key::Named { id: "a" });
+    assert!(Key::Named { id: "z" } < Key::Root);
+    assert_eq!(Key::Root.cmp(&Key::Root), Ordering::Equal);
+    assert_eq!(
+        Key::Named { id: "a" }.partial_cmp(&Key::Index(0, "")),
+        Some(Ordering::Greater)
+    );
+
+    let ranks = [
+        Rank::Unranked,
+        Rank::Name("b"),
+        Rank::Score(2),
+        Rank::Unranked,
+        Rank::Name("a"),
+        Rank::Score(2),
+    ]
+    .into_iter()
+    .collect::<BTreeSet<_>>();
+
+    assert!(matches!(
+        ranks.into_iter().collect::<Vec<_>>().as_slice(),
+        [
+            Rank::Score(2),
+            Rank::Name("a"),
+            Rank::Name("b"),
+            Rank::Unranked
+        ]
+    ));
+}
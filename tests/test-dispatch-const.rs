@@ -0,0 +1,115 @@
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+
+#[penum]
+trait Message {
+    /// The id of the message on the wire.
+    const ID: u32;
+    const NAME: &'static str;
+    const RELIABLE: bool = true;
+
+    fn encode(&self) -> Vec<u8>;
+}
+
+struct Ping;
+struct Chat(String);
+struct Position {
+    x: f32,
+    y: f32,
+}
+
+impl Message for Ping {
+    const ID: u32 = 1;
+    const NAME: &'static str = "ping";
+    const RELIABLE: bool = false;
+
+    fn encode(&self) -> Vec<u8> {
+        vec![Self::ID as u8]
+    }
+}
+
+impl Message for Chat {
+    const ID: u32 = 2;
+    const NAME: &'static str = "chat";
+
+    fn encode(&self) -> Vec<u8> {
+        self.0.as_bytes().to_vec()
+    }
+}
+
+impl Message for Position {
+    const ID: u32 = 3;
+    const NAME: &'static str = "position";
+
+    fn encode(&self) -> Vec<u8> {
+        [self.x.to_le_bytes(), self.y.to_le_bytes()].concat()
+    }
+}
+
+#[penum( (T) | { body: T, .. } | unit where T: ^Message )]
+enum Packet {
+    Ping(Ping),
+    Chat(Chat),
+    Position { body: Position, seq: u64 },
+    Disconnect,
+}
+
+#[penum]
+trait Versioned {
+    const VERSION: u16 = 0;
+}
+
+struct V1;
+struct V2;
+
+impl Versioned for V1 {}
+impl Versioned for V2 {
+    const VERSION: u16 = 2;
+}
+
+#[penum( (T) where T: ^Versioned )]
+enum Schema {
+    V1(V1),
+    V2(V2),
+}
+
+const PING_ID: u32 = Packet::Ping(Ping).id();
+const SCHEMA: u16 = Schema::V2(V2).version();
+
+fn versioned<T: Versioned>(_: &T) -> u16 {
+    T::VERSION
+}
+
+fn main() {
+    assert_eq!(PING_ID, 1);
+    assert_eq!(SCHEMA, 2);
+
+    let chat = Packet::Chat(Chat("hi".to_string()));
+    assert_eq!(chat.id(), 2);
+    assert_eq!(chat.name(), "chat");
+    assert!(chat.reliable());
+
+    let position = Packet::Position {
+        body: Position { x: 0.0, y: 1.0 },
+        seq: 7,
+    };
+    assert_eq!(position.id(), 3);
+    assert_eq!(position.name(), "position");
+
+    assert!(!Packet::Ping(Ping).reliable());
+
+    // The trait isn't implemented, but its methods are still dispatched.
+    assert_eq!(chat.encode(), b"hi");
+    assert_eq!(Packet::Ping(Ping).encode(), vec![1]);
+    assert_eq!(position.encode().len(), 8);
+    assert!(Packet::Disconnect.encode().is_empty());
+
+    // Variants without a dispatched field use the default return value.
+    assert_eq!(Packet::Disconnect.id(), 0);
+    assert_eq!(Packet::Disconnect.name(), "");
+
+    // Consts with a default still let the enum implement the trait.
+    assert_eq!(Schema::V1(V1).version(), 0);
+    assert_eq!(versioned(&Schema::V1(V1)), 0);
+}
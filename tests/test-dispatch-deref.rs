@@ -0,0 +1,112 @@
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[penum]
+trait Eval {
+    fn eval(&self) -> i64;
+    fn depth(&self) -> usize;
+}
+
+struct Lit(i64);
+
+impl Eval for Lit {
+    fn eval(&self) -> i64 {
+        self.0
+    }
+
+    fn depth(&self) -> usize {
+        1
+    }
+}
+
+struct Neg(Expr);
+
+impl Eval for Neg {
+    fn eval(&self) -> i64 {
+        -self.0.eval()
+    }
+
+    fn depth(&self) -> usize {
+        self.0.depth() + 1
+    }
+}
+
+struct Shared(Arc<dyn Eval>);
+
+impl Eval for Shared {
+    fn eval(&self) -> i64 {
+        self.0.eval()
+    }
+
+    fn depth(&self) -> usize {
+        self.0.depth()
+    }
+}
+
+#[penum( (T) | unit where T: ^Eval(deref = all) )]
+enum Expr {
+    Lit(Lit),
+    Neg(Box<Neg>),
+    Rc(Rc<Expr>),
+    Dyn(Arc<dyn Eval>),
+    Shared(Shared),
+    Static(&'static Lit),
+    Opt(Option<Box<Lit>>),
+    Empty,
+}
+
+#[penum]
+trait Reset {
+    fn reset(&mut self);
+    fn take(self) -> i64;
+}
+
+impl Reset for Lit {
+    fn reset(&mut self) {
+        self.0 = 0;
+    }
+
+    fn take(self) -> i64 {
+        self.0
+    }
+}
+
+// Only boxes are seen through, any other wrapper has to implement the
+// trait itself.
+#[penum( (T) where T: ^Reset(deref = [Box]) )]
+enum Slot {
+    Inline(Lit),
+    Boxed(Box<Lit>),
+}
+
+static ANSWER: Lit = Lit(42);
+
+fn main() {
+    let neg = Expr::Neg(Box::new(Neg(Expr::Lit(Lit(2)))));
+    assert_eq!(neg.eval(), -2);
+    assert_eq!(neg.depth(), 2);
+
+    let rc = Expr::Rc(Rc::new(Expr::Neg(Box::new(Neg(Expr::Lit(Lit(3)))))));
+    assert_eq!(rc.eval(), -3);
+
+    let dyn_ = Expr::Dyn(Arc::new(Lit(7)));
+    assert_eq!(dyn_.eval(), 7);
+    assert_eq!(Expr::Shared(Shared(Arc::new(Lit(8)))).eval(), 8);
+
+    assert_eq!(Expr::Static(&ANSWER).eval(), 42);
+
+    // `None` returns the default of the method, like unit variants.
+    assert_eq!(Expr::Opt(Some(Box::new(Lit(5)))).eval(), 5);
+    assert_eq!(Expr::Opt(None).eval(), 0);
+    assert_eq!(Expr::Opt(None).depth(), 0);
+    assert_eq!(Expr::Empty.eval(), 0);
+
+    let mut boxed = Slot::Boxed(Box::new(Lit(9)));
+    boxed.reset();
+    assert_eq!(boxed.take(), 0);
+    assert_eq!(Slot::Inline(Lit(4)).take(), 4);
+}
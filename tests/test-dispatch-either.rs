@@ -0,0 +1,115 @@
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+use std::collections::BTreeSet;
+use std::fmt::Display;
+
+#[penum]
+trait Shape {
+    fn corners(&self) -> impl Iterator<Item = (i32, i32)> + '_;
+}
+
+struct Square {
+    size: i32,
+}
+
+struct Polygon {
+    corners: Vec<(i32, i32)>,
+}
+
+impl Shape for Square {
+    fn corners(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        [
+            (0, 0),
+            (self.size, 0),
+            (self.size, self.size),
+            (0, self.size),
+        ]
+        .into_iter()
+    }
+}
+
+impl Shape for Polygon {
+    fn corners(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.corners.iter().copied()
+    }
+}
+
+#[penum( (T) | unit where T: ^Shape )]
+enum Shapes {
+    Square(Square),
+    Polygon(Polygon),
+    Point,
+}
+
+#[penum]
+trait Labeled {
+    fn label(&self) -> impl Display + '_;
+}
+
+impl Labeled for Square {
+    fn label(&self) -> impl Display + '_ {
+        self.size
+    }
+}
+
+impl Labeled for Polygon {
+    fn label(&self) -> impl Display + '_ {
+        format!("{} corners", self.corners.len())
+    }
+}
+
+#[penum( (T) | unit where T: ^Labeled )]
+enum Labels {
+    Square(Square),
+    Polygon(Polygon),
+    Blank,
+}
+
+#[penum( (T) | unit where T: ^IntoIterator<Item = u32> )]
+enum Ids {
+    List(Vec<u32>),
+    Set(BTreeSet<u32>),
+    Single(Option<u32>),
+    None,
+}
+
+fn main() {
+    let square = Shapes::Square(Square { size: 2 });
+    assert_eq!(square.corners().count(), 4);
+    assert_eq!(square.corners().last(), Some((0, 2)));
+
+    let polygon = Shapes::Polygon(Polygon {
+        corners: vec![(0, 0), (3, 1), (1, 3)],
+    });
+    assert_eq!(
+        polygon.corners().collect::<Vec<_>>(),
+        [(0, 0), (3, 1), (1, 3)]
+    );
+
+    // Unit variants yield an empty iterator instead of panicking.
+    assert_eq!(Shapes::Point.corners().next(), None);
+
+    let polygon = Labels::Polygon(Polygon {
+        corners: vec![(0, 0), (3, 1), (1, 3)],
+    });
+    assert_eq!(polygon.label().to_string(), "3 corners");
+    assert_eq!(Labels::Square(Square { size: 4 }).label().to_string(), "4");
+
+    // Unit variants format as nothing instead of panicking.
+    assert_eq!(Labels::Blank.label().to_string(), "");
+
+    let list = Ids::List(vec![3, 1, 2]);
+    assert_eq!(list.into_iter().collect::<Vec<_>>(), [3, 1, 2]);
+
+    let set = Ids::Set(BTreeSet::from([3, 1, 2]));
+    assert_eq!(set.into_iter().collect::<Vec<_>>(), [1, 2, 3]);
+
+    let mut total = 0;
+    for id in Ids::Single(Some(7)) {
+        total += id;
+    }
+    assert_eq!(total, 7);
+
+    assert_eq!(Ids::None.into_iter().count(), 0);
+}
@@ -21,7 +21,7 @@ impl Cool for i32 {
 enum Mine5 {
     V1(i32),
     V2(i32),
-    V3(i32, i32),
+    V3(#[penum(dispatch)] i32, i32),
 }
 
 fn main() {
@@ -0,0 +1,60 @@
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+use std::error::Error;
+use std::fmt::{self, Debug, Display, Formatter};
+
+#[derive(Debug)]
+struct Timeout;
+
+impl Display for Timeout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "timed out")
+    }
+}
+
+impl Error for Timeout {}
+
+/// Wraps another error, which is reported as its source.
+#[derive(Debug)]
+struct Context(&'static str, Timeout);
+
+impl Display for Context {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for Context {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.1)
+    }
+}
+
+#[penum( (T) | { error: T, .. } | (_, T) where T: ^Error )]
+enum AppError {
+    Timeout(Timeout),
+    Context { error: Context, retries: u8 },
+    Labeled(&'static str, Context),
+}
+
+fn main() {
+    let timeout = AppError::Timeout(Timeout);
+    assert_eq!(timeout.to_string(), "timed out");
+    assert_eq!(format!("{timeout:?}"), "Timeout");
+    assert!(timeout.source().is_none());
+
+    let context = AppError::Context {
+        error: Context("connect", Timeout),
+        retries: 3,
+    };
+    assert_eq!(context.to_string(), "connect");
+    assert_eq!(context.source().unwrap().to_string(), "timed out");
+
+    let labeled = AppError::Labeled("db", Context("query", Timeout));
+    assert_eq!(labeled.to_string(), "query");
+    assert!(labeled.source().is_some());
+
+    let boxed: Box<dyn Error> = Box::new(labeled);
+    assert_eq!(boxed.to_string(), "query");
+}
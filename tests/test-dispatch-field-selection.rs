@@ -0,0 +1,60 @@
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+
+struct Adam;
+struct Eva;
+
+#[penum]
+trait Trait {
+    fn run(&self) -> String;
+}
+
+impl Trait for Adam {
+    fn run(&self) -> String {
+        "Adam".to_string()
+    }
+}
+
+impl Trait for Eva {
+    fn run(&self) -> String {
+        "Eva".to_string()
+    }
+}
+
+// Twin problem, we select the second field with `^`.
+#[penum( (T) | (T, ^T) where T: ^Trait )]
+enum Twins {
+    V1(Adam),
+    V2(Adam, Eva),
+}
+
+// Duck problem, we select the field with `#[penum(dispatch)]`.
+#[penum( (T, U) where T: ^Trait, U: ^Trait )]
+enum Ducks {
+    V1(Adam, #[penum(dispatch)] Eva),
+    V2(#[penum(dispatch)] Eva, Adam),
+}
+
+#[penum( { a: T, b: ^U } where T: ^Trait, U: ^Trait )]
+enum Named {
+    V1 { a: Adam, b: Eva },
+}
+
+// Only one candidate, so there's nothing to select.
+#[penum( (_, T, _) where T: ^Trait )]
+enum Single {
+    V1(i32, Adam, usize),
+}
+
+fn main() {
+    assert_eq!("Adam", Twins::V1(Adam).run());
+    assert_eq!("Eva", Twins::V2(Adam, Eva).run());
+
+    assert_eq!("Eva", Ducks::V1(Adam, Eva).run());
+    assert_eq!("Eva", Ducks::V2(Eva, Adam).run());
+
+    assert_eq!("Eva", Named::V1 { a: Adam, b: Eva }.run());
+
+    assert_eq!("Adam", Single::V1(0, Adam, 0).run());
+}
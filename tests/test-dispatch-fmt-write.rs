@@ -0,0 +1,57 @@
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+use std::fmt::{self, Write};
+
+/// Upper cases everything that is written to it.
+#[derive(Default)]
+struct Shout(String);
+
+impl Write for Shout {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.0.push_str(&s.to_uppercase());
+        Ok(())
+    }
+}
+
+#[penum( (T) where T: ^std::fmt::Write )]
+enum Tuple {
+    Plain(String),
+    Shout(Shout),
+}
+
+#[penum( { out: T, .. } where T: ^fmt::Write )]
+enum Struct {
+    Plain { out: String, lines: usize },
+    Shout { out: Shout },
+}
+
+#[penum( (T) | { out: T } | unit where T: ^fmt::Write )]
+enum Mixed {
+    Plain(String),
+    Shout { out: Shout },
+    Closed,
+}
+
+fn main() {
+    let mut plain = Tuple::Plain(String::new());
+    write!(plain, "{}-{}", 1, "a").unwrap();
+    assert!(matches!(plain, Tuple::Plain(ref s) if s == "1-a"));
+
+    let mut shout = Tuple::Shout(Shout::default());
+    shout.write_char('x').unwrap();
+    assert!(matches!(shout, Tuple::Shout(Shout(ref s)) if s == "X"));
+
+    let mut plain = Struct::Plain {
+        out: String::new(),
+        lines: 0,
+    };
+    writeln!(plain, "hi").unwrap();
+    assert!(matches!(plain, Struct::Plain { ref out, .. } if out == "hi\n"));
+
+    let mut shout = Mixed::Shout {
+        out: Shout::default(),
+    };
+    write!(shout, "{}", "abc").unwrap();
+    assert!(matches!(shout, Mixed::Shout { out: Shout(ref s) } if s == "ABC"));
+}
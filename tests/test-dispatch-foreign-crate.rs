@@ -0,0 +1,125 @@
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+use penum_foreign::shapes::{Shape, Square};
+use penum_foreign::Named;
+
+struct Circle(f32);
+
+impl Named for Circle {
+    fn name(&self) -> String {
+        "circle".to_string()
+    }
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f32 {
+        3.0 * self.0 * self.0
+    }
+}
+
+// `Shape` is tagged in `penum_foreign`, and so is its supertrait `Named`.
+#[penum( (T) | unit where T: ^penum_foreign::shapes::Shape )]
+enum Shapes {
+    Square(Square),
+    Circle(Circle),
+    Empty,
+}
+
+// `net` is a module of this crate, so the enum waits for `net::Handler`
+// to be tagged instead of importing it from a crate called `net`.
+#[penum( (T) where T: ^net::Handler )]
+enum Server {
+    Echo(net::Echo),
+    Upper(net::Upper),
+}
+
+#[penum]
+trait Described {
+    fn describe(&self) -> String;
+}
+
+mod net {
+    use super::Described;
+    use penum::penum;
+
+    #[penum(path = net)]
+    pub trait Handler: Described {
+        fn handle(&self, input: &str) -> String;
+    }
+
+    pub struct Echo;
+    pub struct Upper;
+
+    impl Described for Echo {
+        fn describe(&self) -> String {
+            "echo".to_string()
+        }
+    }
+
+    impl Described for Upper {
+        fn describe(&self) -> String {
+            "upper".to_string()
+        }
+    }
+
+    impl Handler for Echo {
+        fn handle(&self, input: &str) -> String {
+            input.to_string()
+        }
+    }
+
+    impl Handler for Upper {
+        fn handle(&self, input: &str) -> String {
+            input.to_uppercase()
+        }
+    }
+}
+
+mod app {
+    use penum::penum;
+
+    // The enum is in `app`, next to the module of the trait.
+    #[penum( (T) where T: ^net::Route )]
+    pub enum Router {
+        Home(net::Home),
+    }
+
+    pub mod net {
+        use penum::penum;
+
+        #[penum(path = app::net)]
+        pub trait Route {
+            fn path(&self) -> &'static str;
+        }
+
+        pub struct Home;
+
+        impl Route for Home {
+            fn path(&self) -> &'static str {
+                "/"
+            }
+        }
+    }
+}
+
+fn main() {
+    let square = Shapes::Square(Square(2.0));
+    let circle = Shapes::Circle(Circle(1.0));
+
+    assert_eq!(square.area(), 4.0);
+    assert_eq!(circle.area(), 3.0);
+    assert_eq!(square.name(), "square");
+    assert_eq!(circle.name(), "circle");
+
+    let echo = Server::Echo(net::Echo);
+    let upper = Server::Upper(net::Upper);
+
+    use net::Handler;
+    assert_eq!(echo.handle("hi"), "hi");
+    assert_eq!(upper.handle("hi"), "HI");
+    assert_eq!(upper.describe(), "upper");
+
+    use app::net::Route;
+    assert_eq!(app::Router::Home(app::net::Home).path(), "/");
+}
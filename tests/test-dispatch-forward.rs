@@ -0,0 +1,123 @@
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+
+/// Counts down from `n`, with exact size hints and a fast `nth`.
+struct Countdown(usize);
+
+impl Iterator for Countdown {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let n = self.0;
+        self.0 = n.checked_sub(1)?;
+        Some(n)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0, Some(self.0))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<usize> {
+        self.0 = self.0.saturating_sub(n);
+        self.next()
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, usize) -> B,
+    {
+        (1..=self.0).rev().fold(init, |acc, n| f(acc, n))
+    }
+}
+
+#[penum( (T) | { inner: T } where T: ^Iterator )]
+enum Required {
+    Tuple(Countdown),
+    Struct { inner: Countdown },
+}
+
+#[penum( (T) | { inner: T } where T: ^Iterator(forward = all) )]
+enum All {
+    Tuple(Countdown),
+    Struct { inner: Countdown },
+}
+
+#[penum( (T) | { inner: T } where T: ^Iterator(forward = [size_hint, nth]) )]
+enum Listed {
+    Tuple(Countdown),
+    Struct { inner: Countdown },
+}
+
+#[penum]
+trait Greet {
+    fn name(&self) -> String;
+
+    fn greet(&self) -> String {
+        format!("Hello {}", self.name())
+    }
+}
+
+struct English;
+
+impl Greet for English {
+    fn name(&self) -> String {
+        "world".to_string()
+    }
+
+    fn greet(&self) -> String {
+        "Howdy".to_string()
+    }
+}
+
+#[penum( (T) where T: ^Greet )]
+enum Plain {
+    English(English),
+}
+
+#[penum( (T) where T: ^Greet(forward = all) )]
+enum Forwarded {
+    English(English),
+}
+
+fn main() {
+    assert_eq!(Required::Tuple(Countdown(3)).size_hint(), (0, None));
+    assert_eq!(
+        Required::Struct {
+            inner: Countdown(3)
+        }
+        .nth(1),
+        Some(2)
+    );
+    assert_eq!(Required::Tuple(Countdown(3)).sum::<usize>(), 6);
+
+    assert_eq!(All::Tuple(Countdown(3)).size_hint(), (3, Some(3)));
+    assert_eq!(
+        All::Struct {
+            inner: Countdown(5)
+        }
+        .nth(2),
+        Some(3)
+    );
+    assert_eq!(All::Tuple(Countdown(3)).count(), 3);
+    assert_eq!(All::Tuple(Countdown(3)).last(), Some(1));
+    assert_eq!(
+        All::Struct {
+            inner: Countdown(4)
+        }
+        .fold(0, |a, n| a + n),
+        10
+    );
+
+    assert_eq!(
+        Listed::Struct {
+            inner: Countdown(3)
+        }
+        .size_hint(),
+        (3, Some(3))
+    );
+    assert_eq!(Listed::Tuple(Countdown(5)).nth(4), Some(1));
+
+    assert_eq!(Plain::English(English).greet(), "Hello world");
+    assert_eq!(Forwarded::English(English).greet(), "Howdy");
+}
@@ -0,0 +1,78 @@
+#![forbid(unsafe_code)]
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+use std::future::{Future, IntoFuture, Ready};
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+/// Resolves after being polled `0` times.
+struct Countdown(u32);
+
+impl Future for Countdown {
+    type Output = u32;
+
+    fn poll(mut self: std::pin::Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u32> {
+        if self.0 == 0 {
+            return Poll::Ready(42);
+        }
+
+        self.0 -= 1;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+#[penum( (T) where T: ^Future<Output = u32> )]
+enum Job<F: Future<Output = u32>> {
+    Ready(Ready<u32>),
+    Countdown(Countdown),
+    Async(F),
+}
+
+#[penum( (T) | unit where T: ^IntoFuture<Output = u32> )]
+enum Request {
+    Ready(Ready<u32>),
+    Countdown(Countdown),
+    Cancelled,
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+fn is_unpin<T: Unpin>(_: &T) {}
+
+fn main() {
+    let ready: Job<Ready<u32>> = Job::Ready(std::future::ready(1));
+    is_unpin(&ready);
+    assert_eq!(block_on(ready), 1);
+
+    let countdown: Job<Ready<u32>> = Job::Countdown(Countdown(3));
+    assert_eq!(block_on(countdown), 42);
+
+    // Async blocks are `!Unpin`, and are polled in place.
+    let job = Job::Async(async { 7 });
+    assert_eq!(block_on(job), 7);
+
+    let nested = async {
+        let a = Job::Async(async { 1 }).await;
+        let b = Job::<Ready<u32>>::Countdown(Countdown(1)).await;
+        let c = Request::Ready(std::future::ready(100)).await;
+        a + b + c
+    };
+    assert_eq!(block_on(nested), 143);
+
+    assert_eq!(
+        block_on(Request::Ready(std::future::ready(5)).into_future()),
+        5
+    );
+    assert_eq!(block_on(Request::Countdown(Countdown(2)).into_future()), 42);
+}
@@ -0,0 +1,66 @@
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+fn hash_of(value: &impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[penum( (T) | { id: T, .. } | unit where T: ^Hash )]
+enum Key {
+    Id(u64),
+    Name { id: String, note: &'static str },
+    Anonymous,
+}
+
+/// Counts the bytes it has been fed.
+#[derive(Default)]
+struct Counter(u64);
+
+impl Hasher for Counter {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0 += bytes.len() as u64;
+    }
+}
+
+#[penum( (T) | { inner: T } | (_, T) where T: ^Hasher )]
+enum Hashers {
+    Default(DefaultHasher),
+    Counter { inner: Counter },
+    Labeled(&'static str, Counter),
+}
+
+fn main() {
+    assert_eq!(hash_of(&Key::Id(7)), hash_of(&7u64));
+    assert_eq!(
+        hash_of(&Key::Name {
+            id: "penum".to_string(),
+            note: ""
+        }),
+        hash_of(&"penum".to_string())
+    );
+    assert_eq!(hash_of(&Key::Anonymous), hash_of(&()));
+
+    let mut default = Hashers::Default(DefaultHasher::new());
+    42u32.hash(&mut default);
+    assert_eq!(default.finish(), hash_of(&42u32));
+
+    let mut counter = Hashers::Counter {
+        inner: Counter::default(),
+    };
+    counter.write(&[1, 2, 3]);
+    counter.write_u32(9);
+    assert_eq!(counter.finish(), 7);
+
+    let mut labeled = Hashers::Labeled("counter", Counter::default());
+    labeled.write_u8(1);
+    assert_eq!(labeled.finish(), 1);
+}
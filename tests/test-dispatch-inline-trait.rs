@@ -0,0 +1,58 @@
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+
+// Pretend that this module is a third-party crate that we cannot tag
+// with `#[penum]`.
+mod serde_like {
+    pub trait Encode {
+        fn encode(&self, out: &mut Vec<u8>);
+    }
+
+    impl Encode for u8 {
+        fn encode(&self, out: &mut Vec<u8>) {
+            out.push(*self)
+        }
+    }
+
+    impl Encode for String {
+        fn encode(&self, out: &mut Vec<u8>) {
+            out.extend(self.as_bytes())
+        }
+    }
+
+    pub trait Size {
+        type Unit;
+        fn size(&self) -> usize;
+    }
+
+    impl Size for String {
+        type Unit = u8;
+        fn size(&self) -> usize {
+            self.len()
+        }
+    }
+}
+
+use serde_like::{Encode, Size};
+
+#[penum(impl serde_like::Encode { fn encode(&self, out: &mut Vec<u8>); } for { u8, String })]
+enum Message {
+    Byte(u8),
+    Text(String),
+}
+
+#[penum( (T) where T: ^serde_like::Size { type Unit; fn size(&self) -> usize; } )]
+enum Sized {
+    Text(String),
+}
+
+fn main() {
+    let mut out = vec![];
+
+    Message::Byte(1).encode(&mut out);
+    Message::Text("ab".to_string()).encode(&mut out);
+
+    assert_eq!(out, vec![1, b'a', b'b']);
+    assert_eq!(Sized::Text("abc".to_string()).size(), 3);
+}
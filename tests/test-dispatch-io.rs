@@ -0,0 +1,77 @@
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+use std::io::{self, BufRead, Cursor, Read, Seek, SeekFrom, Write};
+
+#[penum( (T) | unit where T: ^Read + ^io::Write + ^Seek )]
+enum Transport {
+    Memory(Cursor<Vec<u8>>),
+    Empty(io::Empty),
+    Closed,
+}
+
+#[penum( { reader: T, .. } | unit where T: ^BufRead )]
+enum Input {
+    Buffered {
+        reader: io::BufReader<Cursor<Vec<u8>>>,
+        lines: usize,
+    },
+    Bytes {
+        reader: &'static [u8],
+    },
+    Closed,
+}
+
+fn main() {
+    let mut memory = Transport::Memory(Cursor::new(Vec::new()));
+    memory.write_all(b"hello world").unwrap();
+    memory.flush().unwrap();
+    assert_eq!(memory.stream_position().unwrap(), 11);
+
+    memory.seek(SeekFrom::Start(6)).unwrap();
+    let mut buf = [0; 5];
+    memory.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"world");
+
+    memory.rewind().unwrap();
+    let mut all = String::new();
+    memory.read_to_string(&mut all).unwrap();
+    assert_eq!(all, "hello world");
+
+    let mut empty = Transport::Empty(io::empty());
+    assert_eq!(empty.write(b"abc").unwrap(), 3);
+    assert_eq!(empty.read(&mut buf).unwrap(), 0);
+
+    let mut closed = Transport::Closed;
+    let unsupported = |err: io::Error| err.kind() == io::ErrorKind::Unsupported;
+    assert!(closed.write_all(b"abc").map_err(unsupported).unwrap_err());
+    assert!(closed
+        .read_exact(&mut buf)
+        .map_err(unsupported)
+        .unwrap_err());
+    assert!(closed
+        .seek(SeekFrom::End(0))
+        .map_err(unsupported)
+        .unwrap_err());
+    assert!(closed.flush().map_err(unsupported).unwrap_err());
+
+    let mut buffered = Input::Buffered {
+        reader: io::BufReader::new(Cursor::new(b"one\ntwo\n".to_vec())),
+        lines: 0,
+    };
+    let mut line = String::new();
+    buffered.read_line(&mut line).unwrap();
+    assert_eq!(line, "one\n");
+    assert_eq!(buffered.fill_buf().unwrap(), b"two\n");
+    buffered.consume(4);
+    assert!(buffered.fill_buf().unwrap().is_empty());
+
+    let mut bytes = Input::Bytes { reader: b"a,b" };
+    let mut chunk = Vec::new();
+    bytes.read_until(b',', &mut chunk).unwrap();
+    assert_eq!(chunk, b"a,");
+
+    let mut closed = Input::Closed;
+    assert!(closed.fill_buf().is_err());
+    closed.consume(1);
+}
@@ -0,0 +1,91 @@
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+
+#[penum( (T) where T: ^DoubleEndedIterator + ^ExactSizeIterator )]
+enum Tuple {
+    Range(std::ops::Range<usize>),
+    Chars(std::vec::IntoIter<usize>),
+}
+
+#[penum( { iter: T, .. } where T: ^DoubleEndedIterator + ^ExactSizeIterator )]
+enum Struct {
+    Range {
+        iter: std::ops::Range<usize>,
+        step: usize,
+    },
+    Vec {
+        iter: std::vec::IntoIter<usize>,
+    },
+}
+
+#[penum( (T, ..) | { iter: T } | unit where T: ^DoubleEndedIterator + ^ExactSizeIterator )]
+enum Mixed {
+    Range(std::ops::Range<usize>, &'static str),
+    Vec { iter: std::vec::IntoIter<usize> },
+    Empty,
+}
+
+#[penum( (T) | { inner: T, .. } | unit where T: ^Extend<char> )]
+enum Sink {
+    Text(String),
+    Chars { inner: Vec<char>, limit: usize },
+    Nothing,
+}
+
+fn main() {
+    let mut tuple = Tuple::Range(0..4);
+    assert_eq!(tuple.len(), 4);
+    assert_eq!(tuple.next_back(), Some(3));
+    assert_eq!(tuple.next(), Some(0));
+    assert_eq!(tuple.len(), 2);
+    assert_eq!(
+        Tuple::Chars(vec![1, 2, 3].into_iter())
+            .rev()
+            .collect::<Vec<_>>(),
+        [3, 2, 1]
+    );
+
+    let mut strukt = Struct::Range {
+        iter: 2..5,
+        step: 1,
+    };
+    assert_eq!(strukt.len(), 3);
+    assert_eq!(strukt.next_back(), Some(4));
+    assert_eq!(
+        Struct::Vec {
+            iter: vec![7, 8].into_iter()
+        }
+        .rev()
+        .next(),
+        Some(8)
+    );
+
+    let mut mixed = Mixed::Range(0..3, "range");
+    assert_eq!(mixed.next_back(), Some(2));
+    assert_eq!(mixed.len(), 2);
+    assert_eq!(
+        Mixed::Vec {
+            iter: vec![5].into_iter()
+        }
+        .next_back(),
+        Some(5)
+    );
+    assert_eq!(Mixed::Empty.next_back(), None);
+    assert_eq!(Mixed::Empty.next(), None);
+
+    let mut text = Sink::Text("ab".to_string());
+    text.extend(['c', 'd']);
+    assert!(matches!(text, Sink::Text(ref s) if s == "abcd"));
+
+    let mut chars = Sink::Chars {
+        inner: vec![],
+        limit: 3,
+    };
+    chars.extend("xy".chars());
+    assert!(matches!(chars, Sink::Chars { ref inner, .. } if inner == &['x', 'y']));
+
+    let mut nothing = Sink::Nothing;
+    nothing.extend("ignored".chars());
+    assert!(matches!(nothing, Sink::Nothing));
+}
@@ -0,0 +1,82 @@
+extern crate penum;
+use penum::penum;
+
+use std::fmt;
+use std::fmt::Display;
+
+struct Ident(&'static str);
+struct Number(i64);
+
+impl fmt::Display for Ident {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[penum( (T) | (T, ..) | { .. } | unit where T: ^Display(fallback(fmt = f.write_str("?"))) )]
+enum Token {
+    Ident(Ident),
+    Number(Number),
+    #[penum(arm(Display::fmt = write!(f, "{}..", self.start())))]
+    Range(Number, Number),
+    #[penum(arm(Display::fmt = f.write_str("\"string\"")))]
+    Str {
+        value: String,
+    },
+    Eof,
+}
+
+impl Token {
+    fn start(&self) -> i64 {
+        match self {
+            Token::Range(start, _) => start.0,
+            _ => 0,
+        }
+    }
+}
+
+#[penum]
+trait Weight {
+    fn weight(&self, scale: u32) -> u32;
+}
+
+struct Light;
+
+impl Weight for Light {
+    fn weight(&self, scale: u32) -> u32 {
+        scale
+    }
+}
+
+// Overridden variants don't need a dispatchable field in strict mode.
+#[penum( strict, (T) | unit where T: ^Weight(fallback(weight = scale * 100)) )]
+enum Cargo {
+    Light(Light),
+    #[penum(arm(Weight::weight = scale * 10))]
+    Heavy,
+    Empty,
+}
+
+fn main() {
+    assert_eq!(Token::Ident(Ident("x")).to_string(), "x");
+    assert_eq!(Token::Number(Number(4)).to_string(), "4");
+    assert_eq!(Token::Range(Number(1), Number(9)).to_string(), "1..");
+    assert_eq!(
+        Token::Str {
+            value: "s".to_string()
+        }
+        .to_string(),
+        "\"string\""
+    );
+    assert_eq!(Token::Eof.to_string(), "?");
+
+    assert_eq!(Cargo::Light(Light).weight(2), 2);
+    assert_eq!(Cargo::Heavy.weight(2), 20);
+    assert_eq!(Cargo::Empty.weight(2), 200);
+}
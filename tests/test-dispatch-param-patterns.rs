@@ -0,0 +1,86 @@
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+
+#[penum]
+trait Mixer {
+    fn ignore(&self, _: u32) -> u32;
+    fn shadow(&self, val: i32, field1: i32) -> i32;
+
+    // Patterns are only allowed in provided methods.
+    fn split(&self, (a, b): (u8, u8)) -> u16 {
+        a as u16 * b as u16
+    }
+    fn bump(&self, mut x: u64) -> u64 {
+        x *= 100;
+        x
+    }
+    fn attributed(&self, #[allow(unused_mut)] mut count: usize) -> usize {
+        count
+    }
+}
+
+struct Plain;
+struct Doubled(u64);
+
+impl Mixer for Plain {
+    fn ignore(&self, _: u32) -> u32 {
+        0
+    }
+    fn split(&self, (a, b): (u8, u8)) -> u16 {
+        a as u16 + b as u16
+    }
+    fn bump(&self, mut x: u64) -> u64 {
+        x += 1;
+        x
+    }
+    fn shadow(&self, val: i32, field1: i32) -> i32 {
+        val - field1
+    }
+    fn attributed(&self, count: usize) -> usize {
+        count
+    }
+}
+
+impl Mixer for Doubled {
+    fn ignore(&self, n: u32) -> u32 {
+        n * 2
+    }
+    fn split(&self, (a, b): (u8, u8)) -> u16 {
+        (a as u16 + b as u16) * 2
+    }
+    fn bump(&self, x: u64) -> u64 {
+        x + self.0
+    }
+    fn shadow(&self, val: i32, field1: i32) -> i32 {
+        (val - field1) * 2
+    }
+    fn attributed(&self, count: usize) -> usize {
+        count * 2
+    }
+}
+
+#[penum( (T, ..) | { mixer: T, .. } where T: ^Mixer(forward = all) )]
+enum Channel {
+    Plain(Plain, i32),
+    Doubled { mixer: Doubled, val: i32 },
+}
+
+fn main() {
+    let plain = Channel::Plain(Plain, 7);
+    assert_eq!(plain.ignore(3), 0);
+    assert_eq!(plain.split((1, 2)), 3);
+    assert_eq!(plain.bump(1), 2);
+    assert_eq!(plain.shadow(5, 3), 2);
+    assert_eq!(plain.attributed(4), 4);
+
+    let doubled = Channel::Doubled {
+        mixer: Doubled(10),
+        val: 1,
+    };
+    assert_eq!(doubled.ignore(3), 6);
+    assert_eq!(doubled.split((1, 2)), 6);
+    assert_eq!(doubled.bump(1), 11);
+    assert_eq!(doubled.shadow(5, 3), 4);
+    assert_eq!(doubled.attributed(4), 8);
+}
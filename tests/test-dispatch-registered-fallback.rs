@@ -0,0 +1,121 @@
+extern crate penum;
+use penum::penum;
+
+use std::borrow::Cow;
+
+#[derive(Debug, PartialEq)]
+enum Status {
+    Up,
+    Unknown,
+}
+
+#[penum::fallback]
+const STATUS_DEFAULT: Status = Status::Unknown;
+
+#[penum::fallback]
+const NAME_DEFAULT: Cow<'static, str> = Cow::Borrowed("offline");
+
+#[penum]
+trait Health {
+    fn status(&self) -> Status;
+    fn last_status(&self) -> &Status;
+    fn name(&self) -> Cow<'static, str>;
+    fn name_ref(&self) -> &Cow<'static, str>;
+}
+
+struct Db(Status, Cow<'static, str>);
+
+impl Health for Db {
+    fn status(&self) -> Status {
+        Status::Up
+    }
+
+    fn last_status(&self) -> &Status {
+        &self.0
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        self.1.clone()
+    }
+
+    fn name_ref(&self) -> &Cow<'static, str> {
+        &self.1
+    }
+}
+
+// Every method has a fallback, so this passes in strict mode.
+#[penum( strict, (T) | unit where T: ^Health )]
+enum Service {
+    Db(Db),
+    Offline,
+}
+
+// Types with the same name in different modules get their own fallback,
+// which can be used from any module.
+mod model {
+    #[derive(Debug, PartialEq)]
+    pub enum Status {
+        Ready,
+        Draft,
+    }
+
+    #[penum::fallback(path = model)]
+    pub const STATUS_DEFAULT: Status = Status::Draft;
+}
+
+mod view {
+    #[derive(Debug, PartialEq)]
+    pub enum Status {
+        Shown,
+        Hidden,
+    }
+
+    #[penum::fallback(path = view)]
+    pub const STATUS_DEFAULT: Status = Status::Hidden;
+}
+
+mod docs {
+    use super::{model, view};
+    use penum::penum;
+
+    #[penum]
+    pub trait Document {
+        fn state(&self) -> model::Status;
+        fn visibility(&self) -> &crate::view::Status;
+    }
+
+    pub struct Page;
+
+    impl Document for Page {
+        fn state(&self) -> model::Status {
+            model::Status::Ready
+        }
+
+        fn visibility(&self) -> &view::Status {
+            &view::Status::Shown
+        }
+    }
+
+    #[penum( strict, (T) | unit where T: ^Document )]
+    pub enum Entry {
+        Page(Page),
+        Missing,
+    }
+}
+
+fn main() {
+    use docs::Document;
+
+    assert_eq!(docs::Entry::Page(docs::Page).state(), model::Status::Ready);
+    assert_eq!(docs::Entry::Missing.state(), model::Status::Draft);
+    assert_eq!(docs::Entry::Missing.visibility(), &view::Status::Hidden);
+
+    let db = Service::Db(Db(Status::Up, Cow::Borrowed("db")));
+    assert_eq!(db.status(), Status::Up);
+    assert_eq!(db.name_ref(), "db");
+
+    assert_eq!(Service::Offline.status(), Status::Unknown);
+    assert_eq!(Service::Offline.last_status(), &Status::Unknown);
+    assert_eq!(Service::Offline.name(), "offline");
+    assert_eq!(Service::Offline.name_ref(), "offline");
+}
@@ -0,0 +1,104 @@
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+use std::cmp::Ordering;
+use std::ops::Add;
+
+#[penum( (T) where T: ^PartialEq + ^Add<Output = Self>(mismatch = panic) )]
+#[derive(Debug, Clone, Copy)]
+enum Number {
+    Int(i64),
+    Float(f64),
+}
+
+#[penum( (T, ..) | { key: T, .. } | unit where T: ^Ord(mismatch = order) )]
+#[derive(Debug)]
+enum Key {
+    Id(u32, &'static str),
+    Name { key: String },
+    Missing,
+}
+
+#[penum( { value: T, .. } where T: ^PartialOrd )]
+#[derive(Debug)]
+enum Reading {
+    Celsius { value: f32 },
+    Kelvin { value: f32, label: String },
+}
+
+// Unit variants are equal to themselves, whatever the mismatch fallback.
+#[penum( (T) | unit where T: ^PartialOrd )]
+#[derive(Debug)]
+enum Slot {
+    Taken(i32),
+    Free,
+    Closed,
+}
+
+fn main() {
+    assert_eq!(Number::Int(2), Number::Int(2));
+    assert_ne!(Number::Int(2), Number::Int(3));
+    // Different variants fall back to `false`.
+    assert_ne!(Number::Int(2), Number::Float(2.0));
+
+    assert_eq!(Number::Int(2) + Number::Int(3), Number::Int(5));
+    assert_eq!(Number::Float(0.5) + Number::Float(1.0), Number::Float(1.5));
+
+    let mixed = std::panic::catch_unwind(|| Number::Int(1) + Number::Float(1.0));
+    assert!(mixed.is_err());
+
+    assert_eq!(Key::Id(1, "a").cmp(&Key::Id(2, "b")), Ordering::Less);
+    assert_eq!(Key::Id(1, "a"), Key::Id(1, "b"));
+    assert!(
+        Key::Id(9, "a")
+            < Key::Name {
+                key: "a".to_string()
+            }
+    );
+    assert!(
+        Key::Name {
+            key: "b".to_string()
+        } > Key::Name {
+            key: "a".to_string()
+        }
+    );
+    assert!(Key::Missing > Key::Id(0, "z"));
+    assert_eq!(Key::Missing, Key::Missing);
+
+    let mut keys = vec![
+        Key::Missing,
+        Key::Name {
+            key: "x".to_string(),
+        },
+        Key::Id(3, "c"),
+        Key::Id(1, "a"),
+    ];
+    keys.sort();
+    assert!(matches!(
+        keys.as_slice(),
+        [Key::Id(1, _), Key::Id(3, _), Key::Name { .. }, Key::Missing]
+    ));
+
+    let cold = Reading::Celsius { value: 1.0 };
+    let warm = Reading::Celsius { value: 2.0 };
+    assert!(cold < warm);
+    assert_eq!(cold.partial_cmp(&warm), Some(Ordering::Less));
+    // Different variants aren't comparable by default.
+    let kelvin = Reading::Kelvin {
+        value: 1.0,
+        label: "k".to_string(),
+    };
+    assert_eq!(cold.partial_cmp(&kelvin), None);
+    assert_ne!(cold, kelvin);
+
+    assert_eq!(Slot::Free, Slot::Free);
+    assert_eq!(Slot::Closed, Slot::Closed);
+    assert_ne!(Slot::Free, Slot::Closed);
+    assert_eq!(Slot::Free.partial_cmp(&Slot::Free), Some(Ordering::Equal));
+    assert_eq!(Slot::Free.partial_cmp(&Slot::Closed), None);
+    assert!(Slot::Free <= Slot::Free);
+    assert!(Slot::Free >= Slot::Free);
+    assert!(!(Slot::Free < Slot::Free));
+    assert_eq!(Slot::Taken(1), Slot::Taken(1));
+    assert_ne!(Slot::Taken(1), Slot::Free);
+}
@@ -0,0 +1,71 @@
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+use std::ops::{Add, Neg, Not};
+
+#[penum( (T) where T: ^Neg<Output = Self> )]
+#[derive(Debug, PartialEq)]
+enum Number {
+    Int(i32),
+    Float(f64),
+}
+
+#[penum( (T, ..) | { value: T, .. } where T: ^Not<Output = Self> )]
+#[derive(Debug, PartialEq)]
+enum Flag {
+    Tagged(bool, &'static str),
+    Named { value: bool, name: String },
+}
+
+#[penum( (T, ..) | unit where T: ^Add<i32, Output = Self> )]
+#[derive(Debug, PartialEq)]
+enum Temperature {
+    Celsius(i32, &'static str),
+    Kelvin(i32, &'static str),
+    Unknown,
+}
+
+#[penum( (T, ..) | { value: T, .. } | unit where T: ^Clone )]
+#[derive(Debug, PartialEq)]
+enum Shared {
+    Text(String, usize),
+    List { value: Vec<u8>, label: String },
+    Empty,
+}
+
+fn main() {
+    assert_eq!(-Number::Int(3), Number::Int(-3));
+    assert_eq!(-Number::Float(1.5), Number::Float(-1.5));
+
+    assert_eq!(!Flag::Tagged(true, "a"), Flag::Tagged(false, "a"));
+    assert_eq!(
+        !Flag::Named {
+            value: false,
+            name: "b".to_string()
+        },
+        Flag::Named {
+            value: true,
+            name: "b".to_string()
+        }
+    );
+
+    assert_eq!(
+        Temperature::Celsius(20, "in") + 5,
+        Temperature::Celsius(25, "in")
+    );
+    assert_eq!(
+        Temperature::Kelvin(0, "out") + 1,
+        Temperature::Kelvin(1, "out")
+    );
+    assert_eq!(Temperature::Unknown + 1, Temperature::Unknown);
+
+    let text = Shared::Text("hi".to_string(), 2);
+    assert_eq!(text.clone(), text);
+
+    let list = Shared::List {
+        value: vec![1, 2],
+        label: "xs".to_string(),
+    };
+    assert_eq!(list.clone(), list);
+    assert_eq!(Shared::Empty.clone(), Shared::Empty);
+}
@@ -0,0 +1,65 @@
+#![deny(unreachable_patterns)]
+extern crate penum;
+use penum::penum;
+
+#[penum]
+trait Buffer {
+    fn bytes(&mut self) -> &mut [u8];
+    fn len(&self) -> usize;
+}
+
+struct Owned(Vec<u8>);
+struct Fixed([u8; 4]);
+
+impl Buffer for Owned {
+    fn bytes(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl Buffer for Fixed {
+    fn bytes(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+
+    fn len(&self) -> usize {
+        4
+    }
+}
+
+// Every variant is dispatched, so nothing falls back to a panic.
+#[penum( strict, (T) where T: ^Buffer )]
+enum Storage {
+    Owned(Owned),
+    Fixed(Fixed),
+}
+
+#[penum]
+trait Tag {
+    const ID: u32;
+}
+
+impl Tag for Owned {
+    const ID: u32 = 1;
+}
+
+// Types with a default can still be returned for unit variants.
+#[penum( strict, (T) | unit where T: ^Tag )]
+enum Maybe {
+    Owned(Owned),
+    Closed,
+}
+
+fn main() {
+    let mut storage = Storage::Fixed(Fixed([0; 4]));
+    storage.bytes()[0] = 7;
+    assert_eq!(storage.bytes(), [7, 0, 0, 0]);
+    assert_eq!(Storage::Owned(Owned(vec![1, 2])).len(), 2);
+
+    assert_eq!(Maybe::Owned(Owned(vec![1])).id(), 1);
+    assert_eq!(Maybe::Closed.id(), 0);
+}
@@ -0,0 +1,40 @@
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+use std::str::{Bytes, Chars};
+use std::vec::IntoIter;
+
+#[penum( (T) where T: ^Iterator(forward = [size_hint], sum = [Item]) )]
+enum Source<'a> {
+    Lines(IntoIter<String>),
+    Bytes(Bytes<'a>),
+    Chars(Chars<'a>),
+    Words(IntoIter<String>),
+}
+
+fn describe(item: SourceItem<'_>) -> String {
+    match item {
+        SourceItem::Lines(line) => format!("line {line}"),
+        SourceItem::Bytes(byte) => format!("byte {byte}"),
+        SourceItem::Chars(char) => format!("char {char}"),
+    }
+}
+
+fn main() {
+    let mut lines = Source::Lines(vec!["a".to_string(), "b".to_string()].into_iter());
+    assert_eq!(lines.next().map(describe).as_deref(), Some("line a"));
+    assert_eq!(lines.last().map(describe).as_deref(), Some("line b"));
+
+    let mut bytes = Source::Bytes("xy".bytes());
+    assert_eq!(bytes.next().map(describe).as_deref(), Some("byte 120"));
+    assert_eq!(bytes.size_hint(), (1, Some(1)));
+
+    let chars = Source::Chars("hi".chars());
+    let described = chars.map(describe).collect::<Vec<_>>();
+    assert_eq!(described, ["char h", "char i"]);
+
+    // Words share the `Lines` variant since they dispatch to the same type.
+    let mut words = Source::Words(vec!["hello".to_string()].into_iter());
+    assert_eq!(words.next().map(describe).as_deref(), Some("line hello"));
+    assert!(words.next().is_none());
+}
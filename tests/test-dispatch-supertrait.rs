@@ -0,0 +1,116 @@
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+use std::ops::{Index, IndexMut};
+
+struct Square(f32);
+struct Circle(f32);
+
+#[penum]
+trait Area {
+    fn area(&self) -> f32;
+}
+
+#[penum]
+trait Named {
+    fn name(&self) -> String;
+}
+
+#[penum]
+trait Shape: Area + Named {
+    fn describe(&self) -> String;
+}
+
+impl Area for Square {
+    fn area(&self) -> f32 {
+        self.0 * self.0
+    }
+}
+impl Area for Circle {
+    fn area(&self) -> f32 {
+        3.0 * self.0 * self.0
+    }
+}
+impl Named for Square {
+    fn name(&self) -> String {
+        "square".to_string()
+    }
+}
+impl Named for Circle {
+    fn name(&self) -> String {
+        "circle".to_string()
+    }
+}
+impl Shape for Square {
+    fn describe(&self) -> String {
+        format!("{} {}", self.name(), self.area())
+    }
+}
+impl Shape for Circle {
+    fn describe(&self) -> String {
+        format!("{} {}", self.name(), self.area())
+    }
+}
+
+// `Area` and `Named` are dispatched through `Shape`.
+#[penum( (T) where T: ^Shape )]
+enum Shapes {
+    Square(Square),
+    Circle(Circle),
+}
+
+// An explicit supertrait bound is only implemented once.
+#[penum( (T) where T: ^Shape + ^Area )]
+enum Explicit {
+    Square(Square),
+    Circle(Circle),
+}
+
+// Trait generics are carried over to the supertrait.
+#[penum( (T) where T: ^IndexMut<usize> )]
+enum Indexed {
+    Vec(Vec<i32>),
+}
+
+// `PartialEq` is the same bound as the `PartialEq<Self>` of `Eq`, since
+// `Rhs` defaults to `Self`.
+#[penum( (T) | unit where T: ^Eq + ^PartialEq )]
+#[derive(Debug)]
+enum Id {
+    Num(u32),
+    Name(String),
+    Anonymous,
+}
+
+// Same for `PartialOrd<Self>` and `PartialEq<Self>` of `Ord`.
+#[penum( (T) where T: ^Ord + ^PartialOrd + ^PartialEq )]
+#[derive(Debug)]
+enum Version {
+    Major(u8),
+    Tag(&'static str),
+}
+
+fn main() {
+    let shapes = [Shapes::Square(Square(2.0)), Shapes::Circle(Circle(1.0))];
+
+    assert_eq!(shapes[0].area(), 4.0);
+    assert_eq!(shapes[1].area(), 3.0);
+    assert_eq!(shapes[0].name(), "square");
+    assert_eq!(shapes[1].name(), "circle");
+    assert_eq!(shapes[1].describe(), "circle 3");
+
+    let explicit = Explicit::Square(Square(3.0));
+    assert_eq!(explicit.area(), 9.0);
+    assert_eq!(explicit.name(), "square");
+
+    assert_eq!(Id::Num(1), Id::Num(1));
+    assert_ne!(Id::Num(1), Id::Name("1".to_string()));
+    assert_eq!(Id::Anonymous, Id::Anonymous);
+
+    assert!(Version::Major(1) < Version::Major(2));
+    assert_eq!(Version::Tag("a").max(Version::Tag("b")), Version::Tag("b"));
+
+    let mut indexed = Indexed::Vec(vec![1, 2, 3]);
+    indexed[1] = 5;
+    assert_eq!(indexed[1], 5);
+}
@@ -0,0 +1,83 @@
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+
+struct Al;
+struct Be(i32);
+struct Ce(usize);
+
+#[penum]
+trait Echo {
+    fn echo(&self) -> String;
+}
+
+#[penum]
+trait AsInner<T> {
+    fn as_inner(&self) -> &T;
+}
+
+impl Echo for Al {
+    fn echo(&self) -> String {
+        "A".to_string()
+    }
+}
+impl Echo for Be {
+    fn echo(&self) -> String {
+        format!("B {}", self.0)
+    }
+}
+impl Echo for Ce {
+    fn echo(&self) -> String {
+        format!("C {}", self.0)
+    }
+}
+
+impl AsInner<i32> for Be {
+    fn as_inner(&self) -> &i32 {
+        &self.0
+    }
+}
+
+impl AsInner<usize> for Ce {
+    fn as_inner(&self) -> &usize {
+        &self.0
+    }
+}
+
+// One `impl Echo` with arms from both `Al` and `Be`.
+#[penum( _ where Al: ^Echo, Be: ^Echo )]
+enum Foo {
+    V1(Al),
+    V2(Be),
+    V3(Ce),
+}
+
+// Same as above, but through an impl expression.
+#[penum( impl Echo for {Be, Ce} )]
+enum Bar {
+    V1(Al),
+    V2(Be),
+    V3(Ce),
+}
+
+// `AsInner<i32>` and `AsInner<usize>` are two different impls.
+#[penum( _ where Be: ^AsInner<i32>, Ce: ^AsInner<usize> )]
+enum Baz {
+    V1(Be),
+    V2(Ce),
+}
+
+fn main() {
+    assert_eq!("A", Foo::V1(Al).echo());
+    assert_eq!("B 2", Foo::V2(Be(2)).echo());
+    assert_eq!("", Foo::V3(Ce(3)).echo());
+
+    assert_eq!("", Bar::V1(Al).echo());
+    assert_eq!("B 2", Bar::V2(Be(2)).echo());
+    assert_eq!("C 3", Bar::V3(Ce(3)).echo());
+
+    assert_eq!(&2, AsInner::<i32>::as_inner(&Baz::V1(Be(2))));
+    assert_eq!(&0, AsInner::<i32>::as_inner(&Baz::V2(Ce(3))));
+    assert_eq!(&0, AsInner::<usize>::as_inner(&Baz::V1(Be(2))));
+    assert_eq!(&3, AsInner::<usize>::as_inner(&Baz::V2(Ce(3))));
+}
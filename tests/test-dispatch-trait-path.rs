@@ -0,0 +1,112 @@
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+
+mod net {
+    use penum::penum;
+
+    #[penum(path = net)]
+    pub trait Handler {
+        fn handle(&self) -> u16;
+    }
+}
+
+mod http {
+    use penum::penum;
+
+    #[penum(path = http)]
+    pub trait Handler {
+        fn handle(&self) -> String;
+    }
+}
+
+// Traits with the same name in different modules are exported next to
+// the trait, e.g. `a::__penum_Codec`, so they don't collide.
+mod a {
+    use penum::penum;
+
+    #[penum]
+    pub trait Codec {
+        fn encode(&self) -> Vec<u8>;
+    }
+}
+
+mod b {
+    use penum::penum;
+
+    #[penum]
+    pub trait Codec {
+        fn decode(&self) -> String;
+    }
+}
+
+// Registered traits shadow std traits with the same name.
+mod app {
+    use penum::penum;
+
+    #[penum(path = app)]
+    pub trait Error {
+        fn code(&self) -> u16;
+    }
+}
+
+struct Tcp;
+struct Udp;
+
+impl net::Handler for Tcp {
+    fn handle(&self) -> u16 {
+        80
+    }
+}
+impl net::Handler for Udp {
+    fn handle(&self) -> u16 {
+        53
+    }
+}
+impl http::Handler for Tcp {
+    fn handle(&self) -> String {
+        "tcp".to_string()
+    }
+}
+impl http::Handler for Udp {
+    fn handle(&self) -> String {
+        "udp".to_string()
+    }
+}
+
+#[penum( (T) where T: ^net::Handler )]
+enum Port {
+    Tcp(Tcp),
+    Udp(Udp),
+}
+
+#[penum( (T) where T: ^crate::http::Handler )]
+enum Protocol {
+    Tcp(Tcp),
+    Udp(Udp),
+}
+
+impl app::Error for Tcp {
+    fn code(&self) -> u16 {
+        500
+    }
+}
+
+#[penum( (T) | unit where T: ^app::Error )]
+enum Failure {
+    Tcp(Tcp),
+    Unknown,
+}
+
+fn main() {
+    assert_eq!(net::Handler::handle(&Port::Tcp(Tcp)), 80);
+    assert_eq!(net::Handler::handle(&Port::Udp(Udp)), 53);
+    assert_eq!(http::Handler::handle(&Protocol::Tcp(Tcp)), "tcp");
+    assert_eq!(http::Handler::handle(&Protocol::Udp(Udp)), "udp");
+
+    assert!(a::__penum_Codec!((stringify)).contains("encode"));
+    assert!(b::__penum_Codec!((stringify)).contains("decode"));
+
+    assert_eq!(app::Error::code(&Failure::Tcp(Tcp)), 500);
+    assert_eq!(app::Error::code(&Failure::Unknown), 0);
+}
@@ -0,0 +1,91 @@
+#![allow(dead_code)]
+extern crate penum;
+use penum::penum;
+
+// The enum can be declared before the traits that it dispatches.
+#[penum( (T) where T: ^Trait + ^Echo + ^Known )]
+enum Must {
+    Static(usize),
+    Text(String),
+}
+
+#[penum]
+trait Known {
+    fn known(&self) -> bool;
+}
+
+impl Known for usize {
+    fn known(&self) -> bool {
+        true
+    }
+}
+impl Known for String {
+    fn known(&self) -> bool {
+        false
+    }
+}
+
+#[penum]
+trait Trait {
+    fn num(&self) -> usize;
+}
+
+impl Trait for usize {
+    fn num(&self) -> usize {
+        *self
+    }
+}
+impl Trait for String {
+    fn num(&self) -> usize {
+        self.len()
+    }
+}
+
+// Supertraits of a trait that comes later are dispatched as well.
+#[penum( (T) where T: ^Loud )]
+enum Speaker {
+    Text(String),
+}
+
+#[penum]
+trait Echo {
+    fn echo(&self) -> String;
+}
+
+impl Echo for usize {
+    fn echo(&self) -> String {
+        self.to_string()
+    }
+}
+impl Echo for String {
+    fn echo(&self) -> String {
+        self.clone()
+    }
+}
+
+#[penum]
+trait Loud: Echo {
+    fn loud(&self) -> String;
+}
+
+impl Loud for String {
+    fn loud(&self) -> String {
+        self.to_uppercase()
+    }
+}
+
+fn main() {
+    let a = Must::Static(10);
+    let b = Must::Text("hello".to_string());
+
+    assert_eq!(a.num(), 10);
+    assert_eq!(b.num(), 5);
+    assert_eq!(a.echo(), "10");
+    assert_eq!(b.echo(), "hello");
+    assert!(a.known());
+    assert!(!b.known());
+
+    let s = Speaker::Text("hey".to_string());
+    assert_eq!(s.echo(), "hey");
+    assert_eq!(s.loud(), "HEY");
+}
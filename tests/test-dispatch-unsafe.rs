@@ -0,0 +1,92 @@
+#![allow(dead_code)]
+#![deny(unsafe_op_in_unsafe_fn)]
+extern crate penum;
+use penum::penum;
+
+/// # Safety
+///
+/// `as_ptr` has to point to `len` initialized bytes.
+#[penum]
+unsafe trait RawBytes {
+    fn len(&self) -> usize;
+    fn as_ptr(&self) -> *const u8;
+
+    /// # Safety
+    ///
+    /// `index` has to be less than `len`.
+    unsafe fn get_unchecked(&self, index: usize) -> u8;
+
+    extern "C" fn checksum(&self) -> u32;
+
+    /// # Safety
+    ///
+    /// Same as `get_unchecked`.
+    unsafe extern "C" fn get_raw(&self, index: usize) -> u8;
+}
+
+struct Inline([u8; 4]);
+struct Heap(Vec<u8>);
+
+unsafe impl RawBytes for Inline {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+    fn as_ptr(&self) -> *const u8 {
+        self.0.as_ptr()
+    }
+    unsafe fn get_unchecked(&self, index: usize) -> u8 {
+        unsafe { *self.0.get_unchecked(index) }
+    }
+    extern "C" fn checksum(&self) -> u32 {
+        self.0.iter().map(|&b| b as u32).sum()
+    }
+    unsafe extern "C" fn get_raw(&self, index: usize) -> u8 {
+        unsafe { *self.as_ptr().add(index) }
+    }
+}
+
+unsafe impl RawBytes for Heap {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+    fn as_ptr(&self) -> *const u8 {
+        self.0.as_ptr()
+    }
+    unsafe fn get_unchecked(&self, index: usize) -> u8 {
+        unsafe { *self.0.get_unchecked(index) }
+    }
+    extern "C" fn checksum(&self) -> u32 {
+        self.0.iter().map(|&b| b as u32 * 2).sum()
+    }
+    unsafe extern "C" fn get_raw(&self, index: usize) -> u8 {
+        unsafe { *self.as_ptr().add(index) }
+    }
+}
+
+#[penum( (T) | { bytes: T } where T: ^RawBytes )]
+enum Buffer {
+    Inline(Inline),
+    Heap { bytes: Heap },
+}
+
+fn sum<T: RawBytes>(bytes: &T) -> u32 {
+    (0..bytes.len())
+        .map(|index| unsafe { bytes.get_unchecked(index) } as u32)
+        .sum()
+}
+
+fn main() {
+    let inline = Buffer::Inline(Inline([1, 2, 3, 4]));
+    let heap = Buffer::Heap {
+        bytes: Heap(vec![5, 6]),
+    };
+
+    assert_eq!(sum(&inline), 10);
+    assert_eq!(sum(&heap), 11);
+
+    assert_eq!(inline.checksum(), 10);
+    assert_eq!(heap.checksum(), 22);
+
+    assert_eq!(unsafe { inline.get_raw(3) }, 4);
+    assert_eq!(unsafe { heap.get_raw(0) }, 5);
+}
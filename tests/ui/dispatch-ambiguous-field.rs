@@ -0,0 +1,19 @@
+extern crate penum;
+
+use penum::penum;
+
+#[penum]
+trait Trait {
+    fn run(&self);
+}
+
+impl Trait for usize {
+    fn run(&self) {}
+}
+
+#[penum( (T, T) where T: ^Trait )]
+enum Twins {
+    V1(usize, usize),
+}
+
+fn main() {}
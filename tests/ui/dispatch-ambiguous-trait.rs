@@ -0,0 +1,28 @@
+extern crate penum;
+
+use penum::penum;
+
+mod net {
+    use penum::penum;
+
+    #[penum(path = net)]
+    pub trait Handler {
+        fn handle(&self);
+    }
+}
+
+mod http {
+    use penum::penum;
+
+    #[penum(path = http)]
+    pub trait Handler {
+        fn handle(&self);
+    }
+}
+
+#[penum( (T) where T: ^Handler )]
+enum Server {
+    V1(usize),
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+extern crate penum;
+
+use penum::penum;
+
+// `Write` could be either `fmt::Write` or `io::Write`.
+#[penum( (T) where T: ^Write )]
+enum Sink {
+    Buffer(String),
+}
+
+fn main() {}
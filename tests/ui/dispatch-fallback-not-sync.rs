@@ -0,0 +1,26 @@
+extern crate penum;
+
+use std::cell::Cell;
+
+use penum::penum;
+
+#[penum]
+trait Counters {
+    fn counters(&self) -> &Vec<Cell<u32>>;
+}
+
+struct Local(Vec<Cell<u32>>);
+
+impl Counters for Local {
+    fn counters(&self) -> &Vec<Cell<u32>> {
+        &self.0
+    }
+}
+
+#[penum( (T) | unit where T: ^Counters )]
+enum Source {
+    Local(Local),
+    Empty,
+}
+
+fn main() {}
@@ -0,0 +1,57 @@
+extern crate penum;
+
+use penum::penum;
+
+trait Named {
+    fn name(&self) -> String;
+}
+
+#[penum]
+trait Source {
+    type Out: Named;
+
+    fn out(&self) -> Self::Out;
+}
+
+struct Disk;
+struct Net;
+struct DiskName;
+struct NetName;
+
+impl Named for DiskName {
+    fn name(&self) -> String {
+        "disk".to_string()
+    }
+}
+
+impl Named for NetName {
+    fn name(&self) -> String {
+        "net".to_string()
+    }
+}
+
+impl Source for Disk {
+    type Out = DiskName;
+
+    fn out(&self) -> DiskName {
+        DiskName
+    }
+}
+
+impl Source for Net {
+    type Out = NetName;
+
+    fn out(&self) -> NetName {
+        NetName
+    }
+}
+
+// `Out` differs per variant, so it becomes a generated type that has to
+// dispatch `Named` as well.
+#[penum( (T) where T: ^Source )]
+enum Src {
+    Disk(Disk),
+    Net(Net),
+}
+
+fn main() {}
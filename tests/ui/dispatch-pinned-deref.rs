@@ -0,0 +1,13 @@
+extern crate penum;
+
+use penum::penum;
+
+// A pinned `Job` doesn't pin the future it borrows, so `poll` can't be
+// projected through the reference.
+#[penum( (T) where T: ^Future<Output = u32>(deref = all) )]
+enum Job<'a, F: std::future::Future<Output = u32>> {
+    Inline(F),
+    Borrowed(&'a mut F),
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+extern crate penum;
+
+use penum::penum;
+use std::future::{Future, Ready};
+
+#[penum( (T) where T: ^Future<Output = u32> )]
+enum Job {
+    Ready(Ready<u32>),
+}
+
+impl Drop for Job {
+    fn drop(&mut self) {}
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+extern crate penum;
+
+use penum::penum;
+
+#[penum]
+trait Buffer {
+    fn bytes(&mut self) -> &mut Vec<u8>;
+    fn len(&self) -> usize;
+}
+
+struct Owned(Vec<u8>);
+
+impl Buffer for Owned {
+    fn bytes(&mut self) -> &mut Vec<u8> {
+        &mut self.0
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[penum( strict, (T) | unit where T: ^Buffer )]
+enum Storage {
+    Owned(Owned),
+    Closed,
+    Empty,
+}
+
+fn main() {}
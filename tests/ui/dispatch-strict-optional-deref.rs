@@ -0,0 +1,31 @@
+extern crate penum;
+
+use penum::penum;
+
+struct Data(Vec<u8>);
+
+#[penum]
+trait Buffer {
+    fn data(&mut self) -> &mut Data;
+    fn len(&self) -> usize;
+}
+
+struct Owned(Data);
+
+impl Buffer for Owned {
+    fn data(&mut self) -> &mut Data {
+        &mut self.0
+    }
+
+    fn len(&self) -> usize {
+        self.0 .0.len()
+    }
+}
+
+// `data` has nothing to return when the `Option` is `None`.
+#[penum( strict, (T) where T: ^Buffer(deref = all) )]
+enum Storage {
+    Owned(Option<Owned>),
+}
+
+fn main() {}
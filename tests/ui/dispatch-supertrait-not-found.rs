@@ -0,0 +1,33 @@
+extern crate penum;
+
+use penum::penum;
+
+trait Area {
+    fn area(&self) -> f32;
+}
+
+#[penum]
+trait Shape: Area {
+    fn name(&self) -> String;
+}
+
+struct Square;
+
+impl Area for Square {
+    fn area(&self) -> f32 {
+        1.0
+    }
+}
+
+impl Shape for Square {
+    fn name(&self) -> String {
+        "square".to_string()
+    }
+}
+
+#[penum( (T) where T: ^Shape )]
+enum Shapes {
+    Square(Square),
+}
+
+fn main() {}
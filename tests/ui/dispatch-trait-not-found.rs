@@ -0,0 +1,25 @@
+extern crate penum;
+
+use penum::penum;
+
+trait Untagged {
+    fn name(&self) -> &'static str;
+}
+
+impl Untagged for usize {
+    fn name(&self) -> &'static str {
+        "usize"
+    }
+}
+
+#[penum( (T) where T: ^Untagged )]
+enum Must {
+    Static(usize),
+}
+
+#[penum( (T) where T: ^std::fmt::Debg )]
+enum Typo {
+    Static(usize),
+}
+
+fn main() {}
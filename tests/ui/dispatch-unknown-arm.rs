@@ -0,0 +1,24 @@
+extern crate penum;
+
+use penum::penum;
+
+use std::fmt::Display;
+
+struct Name(&'static str);
+
+impl Display for Name {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+#[penum( (T) | unit where T: ^Display(fallback(format = f.write_str("?"))) )]
+enum Label {
+    Name(Name),
+    #[penum(arm(Display::to_string = String::new()))]
+    Empty,
+    #[penum(arm(Debug::fmt = f.write_str("none")))]
+    None,
+}
+
+fn main() {}
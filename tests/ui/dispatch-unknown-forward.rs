@@ -0,0 +1,20 @@
+extern crate penum;
+
+use penum::penum;
+
+struct Countdown(usize);
+
+impl Iterator for Countdown {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        None
+    }
+}
+
+#[penum( (T) where T: ^Iterator(forward = [size_hint, rewind]) )]
+enum Counter {
+    V1(Countdown),
+}
+
+fn main() {}
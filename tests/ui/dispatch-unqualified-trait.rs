@@ -0,0 +1,38 @@
+extern crate penum;
+
+use penum::penum;
+
+mod a {
+    use penum::penum;
+
+    #[penum]
+    pub trait Handler {
+        fn handle(&self) -> u16;
+    }
+}
+
+mod b {
+    use penum::penum;
+
+    #[penum]
+    pub trait Handler {
+        fn handle(&self) -> String;
+    }
+}
+
+struct Tcp;
+
+impl a::Handler for Tcp {
+    fn handle(&self) -> u16 {
+        80
+    }
+}
+
+// Both traits are registered as `Handler`, so `a::Handler` can't be told
+// apart from `b::Handler`.
+#[penum( (T) where T: ^a::Handler )]
+enum Port {
+    Tcp(Tcp),
+}
+
+fn main() {}